@@ -0,0 +1,53 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Generates one test per day and part listed in `answers.toml`, and one per
+/// example and part, so that recording an answer is all it takes to have it
+/// checked by `cargo test`.
+fn main() {
+    println!("cargo:rerun-if-changed=answers.toml");
+
+    let answers = fs::read_to_string("answers.toml").expect("failed to read answers.toml");
+    let answers: toml::Table = answers.parse().expect("failed to parse answers.toml");
+
+    let mut tests = String::new();
+
+    for (day, answers) in &answers {
+        for (key, part) in [("part1", "One"), ("part2", "Two")] {
+            if answers.get(key).is_none() {
+                continue;
+            }
+
+            let index = &key[4..];
+            writeln!(
+                tests,
+                "#[test]\nfn day_{day}_part_{index}() {{\n    check_answer(\"{day}\", Part::{part});\n}}\n"
+            )
+            .unwrap();
+        }
+
+        let examples = answers
+            .get("examples")
+            .and_then(|examples| examples.as_table());
+
+        for (name, answers) in examples.into_iter().flatten() {
+            let ident = name.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+            for (key, part) in [("part1", "One"), ("part2", "Two")] {
+                if answers.get(key).is_none() {
+                    continue;
+                }
+
+                let index = &key[4..];
+                writeln!(
+                    tests,
+                    "#[test]\nfn day_{day}_{ident}_part_{index}() {{\n    check_example(\"{day}\", \"{name}\", Part::{part});\n}}\n"
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("answer_tests.rs"), tests)
+        .expect("failed to write answer tests");
+}
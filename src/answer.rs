@@ -0,0 +1,151 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// The answer to one part of a puzzle.
+///
+/// Integers compare by value regardless of signedness, so an answer computed
+/// as an `i64` equals the same number computed as a `usize`. Text equals an
+/// integer only when it is written exactly like it.
+#[derive(Debug, Clone, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    /// Multi-line output, like letters drawn on a grid.
+    Grid(String),
+    Text(String),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Unsigned(n) => i128::try_from(*n).ok(),
+            Answer::Signed(n) => Some(*n),
+            Answer::Grid(_) | Answer::Text(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) | (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Text(text), number) | (number, Answer::Text(text))
+                if matches!(number, Answer::Unsigned(_) | Answer::Signed(_)) =>
+            {
+                *text == number.to_string()
+            }
+            (Answer::Signed(_), _) | (_, Answer::Signed(_)) => {
+                self.as_i128().is_some() && self.as_i128() == other.as_i128()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Grid(s) | Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Reads an answer as written down by hand: integers become numbers, text
+/// spanning several lines becomes a grid and anything else is kept as text.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answer = if let Ok(n) = s.parse::<u128>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i128>() {
+            Answer::Signed(n)
+        } else if s.contains('\n') {
+            Answer::Grid(s.to_string())
+        } else {
+            Answer::Text(s.to_string())
+        };
+
+        Ok(answer)
+    }
+}
+
+/// Integers become numbers and strings are kept as they are written, like
+/// answers computed as text. A string still equals a number written exactly
+/// like it.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Unsigned(u64),
+            Signed(i64),
+            Text(String),
+        }
+
+        let answer = match Raw::deserialize(deserializer)? {
+            Raw::Unsigned(n) => n.into(),
+            Raw::Signed(n) => n.into(),
+            Raw::Text(s) => s.into(),
+        };
+
+        Ok(answer)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($variant:ident, $wide:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(<$wide>::try_from(n).expect("integer fits in 128 bits"))
+                }
+            }
+
+            impl PartialEq<$t> for Answer {
+                fn eq(&self, other: &$t) -> bool {
+                    let other: Answer = (*other).into();
+                    *self == other
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+impl_from_integer!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+/// Keeps text computed by a solution exactly as it is, even when it looks
+/// like a number.
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        if s.contains('\n') {
+            Answer::Grid(s.to_string())
+        } else {
+            Answer::Text(s.to_string())
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    // Numbers equal text only when written exactly alike, so this has to
+    // compare their text.
+    #[allow(clippy::cmp_owned)]
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Grid(s) | Answer::Text(s) => s == other,
+            Answer::Unsigned(_) | Answer::Signed(_) => self.to_string() == *other,
+        }
+    }
+}
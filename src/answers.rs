@@ -0,0 +1,162 @@
+use std::{
+    collections::BTreeMap,
+    fs::{read_to_string, write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use crate::{answer::Answer, input::InputSource, runner::Part};
+
+/// The known answers for every day, stored in `answers.toml` at the crate root
+/// and keyed by zero-padded day number.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<String, DayAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DayAnswers {
+    /// Hash of the input the answers were computed from, see [`input_hash`].
+    pub input: Option<String>,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    /// Answers for the named inputs in `input/examples/{day}`.
+    #[serde(default)]
+    pub examples: BTreeMap<String, ExampleAnswers>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExampleAnswers {
+    /// The example input to read, when it isn't named like the table. This
+    /// lets one input be checked with different parameters.
+    pub file: Option<String>,
+    /// Parameters that differ from the day's defaults for this example.
+    #[serde(default)]
+    pub params: BTreeMap<String, toml::Value>,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl DayAnswers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        select(self.part1.as_ref(), self.part2.as_ref(), part)
+    }
+
+    /// Whether the answers were recorded for `input`. Answers without a hash
+    /// are assumed to match.
+    pub fn matches_input(&self, input: &str) -> bool {
+        self.input
+            .as_ref()
+            .is_none_or(|hash| *hash == input_hash(input))
+    }
+}
+
+impl ExampleAnswers {
+    pub fn get(&self, part: Part) -> Option<&Answer> {
+        select(self.part1.as_ref(), self.part2.as_ref(), part)
+    }
+
+    /// The source of the example stored under `name`.
+    pub fn source(&self, day: &str, name: &str) -> InputSource {
+        InputSource::Example {
+            day: day.to_string(),
+            name: self.file.as_deref().unwrap_or(name).to_string(),
+        }
+    }
+
+    /// The parameters of the example, as they would be given to `--param`.
+    pub fn param_overrides(&self) -> BTreeMap<String, String> {
+        self.params
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+}
+
+fn select<'a>(
+    part1: Option<&'a Answer>,
+    part2: Option<&'a Answer>,
+    part: Part,
+) -> Option<&'a Answer> {
+    match part {
+        Part::One => part1,
+        Part::Two => part2,
+    }
+}
+
+impl Answers {
+    pub fn load() -> Result<Self> {
+        let file = path();
+        let contents =
+            read_to_string(&file).context(format!("Failed to read {}", file.display()))?;
+
+        toml::from_str(&contents).context(format!("Failed to parse {}", file.display()))
+    }
+
+    pub fn day(&self, day: &str) -> Option<&DayAnswers> {
+        self.days.get(day)
+    }
+
+    /// Writes a newly computed answer to `answers.toml`, keeping the rest of the
+    /// file, including comments, as it is.
+    pub fn record(day: &str, part: Part, answer: &Answer, input: &str) -> Result<()> {
+        let file = path();
+        let contents =
+            read_to_string(&file).context(format!("Failed to read {}", file.display()))?;
+        let mut document: DocumentMut = contents
+            .parse()
+            .context(format!("Failed to parse {}", file.display()))?;
+
+        let table = document
+            .entry(day)
+            .or_insert(Item::Table(Table::new()))
+            .as_table_mut()
+            .context(format!("Day {day} in {} is not a table", file.display()))?;
+
+        table["input"] = value(input_hash(input));
+        // TOML integers are 64-bit, so larger numbers are written as text,
+        // which still compares equal to them.
+        let integer = match *answer {
+            Answer::Unsigned(n) => i64::try_from(n).ok(),
+            Answer::Signed(n) => i64::try_from(n).ok(),
+            Answer::Grid(_) | Answer::Text(_) => None,
+        };
+        table[key(part)] = match integer {
+            Some(n) => value(n),
+            None => value(answer.to_string()),
+        };
+
+        write(&file, document.to_string()).context(format!("Failed to write {}", file.display()))
+    }
+}
+
+fn key(part: Part) -> &'static str {
+    match part {
+        Part::One => "part1",
+        Part::Two => "part2",
+    }
+}
+
+fn path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+}
+
+/// A 64-bit FNV-1a hash of the input. It only needs to tell inputs apart, but
+/// unlike `DefaultHasher` it is stable across Rust versions.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    format!("{hash:016x}")
+}
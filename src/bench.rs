@@ -0,0 +1,99 @@
+use std::{fmt::Display, time::Duration};
+
+use anyhow::{bail, ensure, Result};
+
+use crate::{
+    params::Params,
+    runner::{self, Part, Status},
+    solution::DynSolution,
+};
+
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Result<Self> {
+        ensure!(
+            !samples.is_empty(),
+            "cannot compute statistics without samples"
+        );
+
+        samples.sort();
+
+        let runs = samples.len();
+        let min = samples[0];
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        let secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        #[allow(clippy::cast_precision_loss)]
+        let count = runs as f64;
+        let mean = secs.iter().sum::<f64>() / count;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+
+        Ok(Self {
+            runs,
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "runs    {}", self.runs)?;
+        writeln!(f, "min     {:.2?}", self.min)?;
+        writeln!(f, "median  {:.2?}", self.median)?;
+        writeln!(f, "mean    {:.2?}", self.mean)?;
+        write!(f, "stddev  {:.2?}", self.std_dev)
+    }
+}
+
+/// Runs a part `warmup` times without measuring, then `runs` times collecting
+/// the elapsed time of each run. Fails on the first run that doesn't produce
+/// an answer.
+pub fn bench(
+    day: &str,
+    solution: &'static (dyn DynSolution + Sync),
+    part: Part,
+    input: &str,
+    params: &Params,
+    warmup: usize,
+    runs: usize,
+) -> Result<Stats> {
+    ensure!(runs > 0, "at least one run is required");
+
+    let mut samples = Vec::with_capacity(runs);
+
+    for i in 0..warmup + runs {
+        let outcome = runner::run(day, solution, part, input, params, None);
+
+        match outcome.status {
+            Status::Ok(_) => (),
+            Status::Error(err) => return Err(err),
+            Status::NotImplemented => bail!("Day {day} part {part} is not implemented yet"),
+            Status::Panicked(panic) => bail!("Day {day} part {part} {panic}"),
+            Status::TimedOut(_) => unreachable!("benchmarks run without a timeout"),
+            Status::MemoryExceeded(_) => unreachable!("benchmarks run without a memory limit"),
+        }
+
+        if i >= warmup {
+            samples.push(outcome.elapsed());
+        }
+    }
+
+    Stats::from_samples(samples)
+}
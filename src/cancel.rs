@@ -0,0 +1,68 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+thread_local! {
+    static CURRENT: RefCell<CancelToken> = RefCell::new(CancelToken::new());
+}
+
+/// Cooperative cancellation for long-running solutions. The runner cancels
+/// the token when a call runs past its timeout, and solutions poll it with
+/// [`CancelToken::check`] to stop early.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The token of the run on this thread. Solutions that spread work over
+    /// other threads need to fetch it before doing so.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Makes this the token returned by [`CancelToken::current`] on this
+    /// thread.
+    pub fn make_current(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.clone());
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fails with [`Cancelled`] once the token has been cancelled.
+    #[allow(dead_code)]
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            return Err(Cancelled.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned by [`CancelToken::check`] after cancellation.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl Error for Cancelled {}
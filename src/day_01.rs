@@ -1,14 +1,22 @@
 use anyhow::{Context, Result};
 use regex::Regex;
 
-use crate::solution::Solution;
+use crate::{answer::Answer, params::Params, registry::Registration, solution::Solution};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(1, "Trebuchet?!", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let calibration_values: Vec<usize> = input
-            .lines()
+    type Parsed = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(&self, lines: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let calibration_values: Vec<usize> = lines
+            .iter()
             .map(|line| {
                 let digits: Vec<char> = line.chars().filter(char::is_ascii_digit).collect();
 
@@ -29,12 +37,12 @@ impl Solution for Day {
 
         let total_value: usize = calibration_values.iter().sum();
 
-        Ok(total_value.to_string())
+        Ok(total_value.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let calibration_values: Vec<usize> = input
-            .lines()
+    fn part2(&self, lines: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let calibration_values: Vec<usize> = lines
+            .iter()
             .map(|line| {
                 let re_str =
                     r"one|two|three|four|five|six|seven|eight|nine|zero|1|2|3|4|5|6|7|8|9|0";
@@ -49,7 +57,7 @@ impl Solution for Day {
                 let re_str = re_str.chars().rev().collect::<String>();
                 let re = Regex::new(&re_str)?;
 
-                let reverse_line = line.to_string().chars().rev().collect::<String>();
+                let reverse_line = line.chars().rev().collect::<String>();
 
                 let last = re
                     .find(&reverse_line)
@@ -65,7 +73,7 @@ impl Solution for Day {
 
         let total_value: usize = calibration_values.iter().sum();
 
-        Ok(total_value.to_string())
+        Ok(total_value.into())
     }
 }
 
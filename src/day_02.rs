@@ -1,21 +1,27 @@
 use std::{cmp, str::FromStr};
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use itertools::Itertools;
 use nom::{
-    bytes::complete::{is_a, tag},
-    character::complete::{alpha1, digit1, multispace0},
-    combinator::{all_consuming, map_res},
+    bytes::complete::is_a,
+    character::complete::{alpha1, multispace0},
     multi::separated_list1,
-    Finish, IResult,
 };
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::{Param, Params},
+    parsing::{parse_all, parse_lines, tag, unsigned, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(2, "Cube Conundrum", &Day {}));
+
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: usize,
     sets: Vec<Set>,
 }
@@ -48,7 +54,7 @@ impl Game {
 }
 
 fn parse_color(input: &str) -> IResult<&str, (usize, &str)> {
-    let (input, n) = map_res(digit1, str::parse::<usize>)(input)?;
+    let (input, n) = unsigned(input)?;
     let (input, _) = multispace0(input)?;
     let (input, color) = alpha1(input)?;
 
@@ -73,7 +79,7 @@ fn parse_set(input: &str) -> IResult<&str, Set> {
 
 fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, _) = tag("Game ")(input)?;
-    let (input, id) = map_res(digit1, str::parse)(input)?;
+    let (input, id) = unsigned(input)?;
     let (input, _) = is_a(": ")(input)?;
     let (input, sets) = separated_list1(tag("; "), parse_set)(input)?;
 
@@ -81,22 +87,34 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
 }
 
 impl FromStr for Game {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match all_consuming(parse_game)(s).finish() {
-            Ok((_, game)) => Ok(game),
-            Err(err) => bail!("failed to parse line '{s}' with error: {err}"),
-        }
+        parse_all(s, parse_game)
     }
 }
 
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let games: Vec<Game> = input.lines().map(str::parse).collect::<Result<_>>()?;
-        let (max_red, max_green, max_blue) = (12_usize, 13_usize, 14_usize);
+    type Parsed = Vec<Game>;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new::<usize>("red", "12", "red cubes in the bag"),
+        Param::new::<usize>("green", "13", "green cubes in the bag"),
+        Param::new::<usize>("blue", "14", "blue cubes in the bag"),
+    ];
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(&self, games: &Self::Parsed, params: &Params) -> Result<Answer> {
+        let (max_red, max_green, max_blue) = (
+            params.get::<usize>("red")?,
+            params.get::<usize>("green")?,
+            params.get::<usize>("blue")?,
+        );
         let possible_games = games
-            .into_iter()
+            .iter()
             .filter(|g| {
                 g.sets
                     .iter()
@@ -105,15 +123,14 @@ impl Solution for Day {
             .collect_vec();
         let result = possible_games.iter().map(|g| g.id).sum::<usize>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let games: Vec<Game> = input.lines().map(str::parse).collect::<Result<_>>()?;
-        let min_sets = games.into_iter().map(|g| g.calculate_min_set());
+    fn part2(&self, games: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let min_sets = games.iter().map(Game::calculate_min_set);
         let powers = min_sets.map(|s| s.calculate_power());
         let result = powers.sum::<usize>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 }
@@ -3,29 +3,36 @@ use std::str::FromStr;
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer, geometry::UPoint, grid::Grid, params::Params, parsing::ParseError,
+    registry::Registration, solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(3, "Gear Ratios", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let map: Map = input.parse()?;
+    type Parsed = Map;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
 
+    fn part1(&self, map: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let parts = map.get_parts();
 
         let result: usize = parts.into_iter().sum();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let map: Map = input.parse()?;
-
+    fn part2(&self, map: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let gear_parts = map.get_gear_parts();
 
         let result: usize = gear_parts.into_iter().map(|(a, b)| a * b).sum();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 }
 
@@ -36,68 +43,44 @@ enum Type {
     Symbol(char),
 }
 
-struct Map {
-    grid: Vec<Vec<Type>>,
-    numbers: Vec<(usize, Vec<Pos>)>,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
-struct Pos {
-    y: usize,
-    x: usize,
+pub struct Map {
+    grid: Grid<Type>,
+    numbers: Vec<(usize, Vec<UPoint>)>,
 }
 
 impl FromStr for Map {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         use Type::{Digit, Empty, Symbol};
 
-        let grid = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '.' => Empty,
-                        d if d.is_ascii_digit() => Digit(d.to_digit(10).unwrap() as usize),
-                        s => Symbol(s),
-                    })
-                    .collect_vec()
+        let grid = Grid::parse(s, "a cell", |c| {
+            Some(match c {
+                '.' => Empty,
+                d if d.is_ascii_digit() => Digit(d.to_digit(10).unwrap() as usize),
+                s => Symbol(s),
             })
-            .collect_vec();
+        })?;
 
-        let mut numbers: Vec<(usize, Vec<Pos>)> = Vec::new();
+        let mut numbers: Vec<(usize, Vec<UPoint>)> = Vec::new();
 
-        for (y, line) in grid.iter().enumerate() {
-            let mut tmp_pos: Vec<Pos> = vec![];
+        for (y, line) in grid.rows().enumerate() {
+            let mut tmp_pos: Vec<UPoint> = vec![];
             let mut tmp_number: Vec<usize> = vec![];
 
             for (x, val) in line.iter().enumerate() {
-                match val {
-                    Digit(d) => {
-                        tmp_number.push(*d);
-                        tmp_pos.push(Pos { y, x });
-                        if x == line.len() - 1 && !tmp_pos.is_empty() && !tmp_number.is_empty() {
-                            let number = tmp_number.iter().join("").parse().unwrap();
-                            let pos = tmp_pos.iter().copied().collect_vec();
-
-                            tmp_pos.clear();
-                            tmp_number.clear();
-
-                            numbers.push((number, pos));
-                        }
-                    }
-                    _ => {
-                        if !tmp_pos.is_empty() && !tmp_number.is_empty() {
-                            let number = tmp_number.iter().join("").parse().unwrap();
-                            let pos = tmp_pos.iter().copied().collect_vec();
-
-                            tmp_pos.clear();
-                            tmp_number.clear();
-
-                            numbers.push((number, pos));
-                        }
-                    }
+                if let Digit(d) = val {
+                    tmp_number.push(*d);
+                    tmp_pos.push(UPoint::new(x, y));
+                }
+
+                let ends_number = !matches!(val, Digit(_)) || x == line.len() - 1;
+                if ends_number && !tmp_number.is_empty() {
+                    let number = tmp_number.iter().join("").parse().unwrap();
+                    numbers.push((number, tmp_pos.clone()));
+
+                    tmp_pos.clear();
+                    tmp_number.clear();
                 }
             }
         }
@@ -107,14 +90,10 @@ impl FromStr for Map {
 }
 
 impl Map {
-    fn get_entry(&self, pos: &Pos) -> Option<&Type> {
-        self.grid.get(pos.y).and_then(|line| line.get(pos.x))
-    }
-
-    fn contains_symbol(&self, positions: &[Pos]) -> bool {
+    fn contains_symbol(&self, positions: &[UPoint]) -> bool {
         positions
             .iter()
-            .any(|pos| matches!(self.get_entry(pos), Some(Type::Symbol(_))))
+            .any(|&pos| matches!(self.grid.get(pos), Some(Type::Symbol(_))))
     }
 
     fn get_parts(&self) -> Vec<usize> {
@@ -134,12 +113,9 @@ impl Map {
     fn get_gear_parts(&self) -> Vec<(usize, usize)> {
         self.grid
             .iter()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.iter().enumerate().filter_map(move |(x, t)| match t {
-                    Type::Symbol('*') => Some(Pos { y, x }),
-                    _ => None,
-                })
+            .filter_map(|(pos, t)| match t {
+                Type::Symbol('*') => Some(pos),
+                _ => None,
             })
             .filter_map(|position| {
                 let neighbours = self.calculate_neighbours(&[position]);
@@ -163,43 +139,10 @@ impl Map {
             .collect_vec()
     }
 
-    fn calculate_neighbours(&self, number_positions: &[Pos]) -> Vec<Pos> {
-        let diffs = [
-            (-1, 0),
-            (-1, -1),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-
+    fn calculate_neighbours(&self, number_positions: &[UPoint]) -> Vec<UPoint> {
         number_positions
             .iter()
-            .flat_map(|pos| {
-                diffs
-                    .iter()
-                    .map(|diff| {
-                        let y = i32::try_from(pos.y).unwrap() + diff.0;
-                        let x = i32::try_from(pos.x).unwrap() + diff.1;
-
-                        if x >= 0
-                            && y >= 0
-                            && x < i32::try_from(self.grid[0].len()).unwrap()
-                            && y < i32::try_from(self.grid.len()).unwrap()
-                        {
-                            Some(Pos {
-                                y: usize::try_from(y).unwrap(),
-                                x: usize::try_from(x).unwrap(),
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect_vec()
-            })
-            .flatten()
+            .flat_map(|&pos| self.grid.neighbours8(pos))
             .filter(|pos| !number_positions.contains(pos))
             .unique()
             .sorted()
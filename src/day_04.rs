@@ -1,43 +1,43 @@
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use itertools::Itertools;
 use nom::{
-    bytes::complete::{tag, take_until},
-    character::complete::{digit1, space0},
-    combinator::{all_consuming, map_res},
-    multi::many1,
-    sequence::separated_pair,
-    Finish, IResult,
+    character::complete::{char, space1},
+    sequence::{separated_pair, tuple},
 };
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::Params,
+    parsing::{numbers, parse_all, parse_lines, tag, unsigned, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(4, "Scratchcards", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let cards: Vec<Card> = input
-            .lines()
-            .map(str::parse::<Card>)
-            .collect::<Result<_>>()?;
+    type Parsed = Vec<Card>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(parse_lines(input)?)
+    }
 
+    fn part1(&self, cards: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let scores = cards.iter().map(Card::calculate_score).collect_vec();
 
         let result = scores.iter().sum::<usize>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let cards: Vec<Card> = input
-            .lines()
-            .map(str::parse::<Card>)
-            .collect::<Result<_>>()?;
-
+    fn part2(&self, cards: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let mut number_of_cards = vec![1; cards.len()];
 
-        for (i, card) in cards.into_iter().enumerate() {
+        for (i, card) in cards.iter().enumerate() {
             let score = card.calculate_matching_numbers();
             for j in i + 1..i + 1 + score {
                 number_of_cards[j] += number_of_cards[i];
@@ -46,12 +46,12 @@ impl Solution for Day {
 
         let result = number_of_cards.iter().sum::<usize>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 }
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     winning_numbers: Vec<usize>,
     card_numbers: Vec<usize>,
 }
@@ -73,38 +73,17 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match all_consuming(parse_card)(s).finish() {
-            Ok((_, card)) => Ok(card),
-            Err(err) => bail!("failed to parse card '{s}' with error: {err}"),
-        }
+        parse_all(s, parse_card)
     }
 }
 
-fn parse_number(s: &str) -> IResult<&str, usize> {
-    let (s, _) = space0(s)?;
-    let (s, number) = map_res(digit1, str::parse::<usize>)(s)?;
-    let (s, _) = space0(s)?;
-
-    Ok((s, number))
-}
-
-fn parse_number_list(s: &str) -> IResult<&str, Vec<usize>> {
-    let (s, _) = space0(s)?;
-    let (s, numbers) = many1(parse_number)(s)?;
-    let (s, _) = space0(s)?;
-
-    Ok((s, numbers))
-}
-
 fn parse_card(s: &str) -> IResult<&str, Card> {
-    let (s, _) = take_until(":")(s)?;
-    let (s, _) = tag(":")(s)?;
-    let (s, _) = space0(s)?;
+    let (s, _) = tuple((tag("Card"), space1, unsigned::<usize>, char(':'), space1))(s)?;
     let (s, (winning_numbers, card_numbers)) =
-        separated_pair(parse_number_list, tag("|"), parse_number_list)(s)?;
+        separated_pair(numbers, tuple((space1, char('|'), space1)), numbers)(s)?;
 
     Ok((
         s,
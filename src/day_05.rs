@@ -1,202 +1,567 @@
 use anyhow::{bail, Context, Result};
-use core::panic;
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, digit1, newline, space1},
-    combinator::{all_consuming, map_opt, map_res, opt},
-    multi::separated_list1,
-    sequence::{separated_pair, tuple},
-    Finish, IResult,
+    character::complete::{alpha1, space1},
+    sequence::{preceded, separated_pair, tuple},
+    Offset,
 };
-use rayon::prelude::*;
-use std::{collections::HashMap, str::FromStr};
+use std::{fmt::Display, ops::Range, str::FromStr};
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::{Param, Params},
+    parsing::{blocks, labelled, lines, numbers, parse_all, tag, unsigned, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(
+    5,
+    "If You Give A Seed A Fertilizer",
+    &Day {}
+));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        use Type::{Fertilizer, Humidity, Light, Location, Seed, Soil, Temperature, Water};
-
-        let almanac: Almanac = input.parse()?;
-        let pairs = vec![
-            (Seed, Soil),
-            (Soil, Fertilizer),
-            (Fertilizer, Water),
-            (Water, Light),
-            (Light, Temperature),
-            (Temperature, Humidity),
-            (Humidity, Location),
-        ];
-
-        let locations = pairs
-            .iter()
-            .map(|pair| almanac.conversion_maps.get(pair))
-            .fold_options(almanac.seeds, |acc, conversion_map| {
-                acc.into_iter().map(|x| conversion_map.convert(x)).collect()
-            })
-            .context("failed to convert from seeds to location")?;
-
-        let result = locations.into_iter().min().context("no lowest value")?;
-
-        Ok(result.to_string())
-    }
-
-    fn compute_2(&self, input: &str) -> Result<String> {
-        use Type::{Fertilizer, Humidity, Light, Location, Seed, Soil, Temperature, Water};
-
-        let almanac: Almanac = input.parse()?;
-        let conversion_maps = [
-            (Seed, Soil),
-            (Soil, Fertilizer),
-            (Fertilizer, Water),
-            (Water, Light),
-            (Light, Temperature),
-            (Temperature, Humidity),
-            (Humidity, Location),
-        ]
-        .iter()
-        .map(|pair| almanac.conversion_maps.get(pair))
-        .collect::<Option<Vec<&ConversionMap>>>()
-        .context("failed to get all conversion maps")?;
+    type Parsed = Almanac;
+
+    const PARAMS: &'static [Param] = &[
+        Param::new::<String>("source", "seed", "category the seed numbers are in"),
+        Param::new::<String>(
+            "target",
+            "location",
+            "category to find the lowest number in",
+        ),
+        Param::new::<Locate>(
+            "locate",
+            "",
+            "a number or start..end range in the target category to trace back to the seeds \
+             that lead there, instead of finding the lowest number",
+        ),
+    ];
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, almanac: &Self::Parsed, params: &Params) -> Result<Answer> {
+        let chain = chain(almanac, params)?;
+
+        if let Locate(Some(target)) = params.get("locate")? {
+            let sources = chain.preimage(target);
+            let seeds = almanac
+                .seeds
+                .iter()
+                .filter(|&&seed| sources.iter().any(|range| range.contains(&seed)))
+                .sorted()
+                .dedup()
+                .map(|&seed| seed..seed + 1)
+                .collect_vec();
 
-        let locations: Vec<usize> = almanac
+            return Ok(format_ranges(&seeds));
+        }
+
+        let result = almanac
             .seeds
-            .chunks_exact(2)
-            .par_bridge()
-            .flat_map(|pair| {
-                let [start, range] = *pair else {
-                    panic!("This can't happen")
-                };
-                let seeds: Vec<_> = (start..(start + range)).collect();
+            .iter()
+            .map(|&seed| chain.convert(seed))
+            .min()
+            .context("no lowest value")?;
+
+        Ok(result.into())
+    }
+
+    fn part2(&self, almanac: &Self::Parsed, params: &Params) -> Result<Answer> {
+        let chain = chain(almanac, params)?;
+        let seeds = almanac.seed_ranges();
+
+        if let Locate(Some(target)) = params.get("locate")? {
+            let sources = chain.preimage(target);
+            return Ok(format_ranges(&intersect(&seeds, &sources)));
+        }
 
-                conversion_maps.iter().fold(seeds, |acc, conversion_map| {
-                    acc.par_iter().map(|x| conversion_map.convert(*x)).collect()
-                })
-            })
-            .collect();
+        let result = seeds
+            .into_iter()
+            .flat_map(|range| chain.convert_range(range))
+            .filter(|range| !range.is_empty())
+            .map(|range| range.start)
+            .min()
+            .context("no lowest value")?;
 
-        let result = locations.into_iter().min().context("no lowest value")?;
+        Ok(result.into())
+    }
+}
+
+/// The composed map between the categories given by the parameters.
+fn chain(almanac: &Almanac, params: &Params) -> Result<ConversionMap> {
+    almanac.chain(
+        &params.get::<String>("source")?,
+        &params.get::<String>("target")?,
+    )
+}
+
+/// The `locate` parameter: the numbers in the target category to trace back,
+/// if any.
+struct Locate(Option<Range<usize>>);
+
+impl FromStr for Locate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self(None));
+        }
 
-        Ok(result.to_string())
+        let parse = |n: &str| {
+            n.parse::<usize>()
+                .map_err(|_| "expected a number or start..end".to_string())
+        };
+        let range = if let Some((start, end)) = s.split_once("..") {
+            let range = parse(start)?..parse(end)?;
+            if range.is_empty() {
+                return Err("start must be below end".to_string());
+            }
+            range
+        } else {
+            let n = parse(s)?;
+            n..n.checked_add(1).ok_or("the number is too large")?
+        };
+
+        Ok(Self(Some(range)))
+    }
+}
+
+/// Lists sorted ranges, with single values on their own, like `1, 4..7`.
+fn format_ranges(ranges: &[Range<usize>]) -> Answer {
+    if ranges.is_empty() {
+        return "none".into();
     }
+
+    ranges
+        .iter()
+        .map(|range| match range.len() {
+            1 => range.start.to_string(),
+            _ => format!("{}..{}", range.start, range.end),
+        })
+        .join(", ")
+        .into()
+}
+
+/// The values in both `a` and `b`, which must each be sorted and disjoint.
+fn intersect(a: &[Range<usize>], b: &[Range<usize>]) -> Vec<Range<usize>> {
+    a.iter()
+        .cartesian_product(b)
+        .map(|(a, b)| a.start.max(b.start)..a.end.min(b.end))
+        .filter(|range| !range.is_empty())
+        .sorted_by_key(|range| range.start)
+        .collect()
 }
 
 #[derive(Debug)]
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<usize>,
-    conversion_maps: HashMap<(Type, Type), ConversionMap>,
+    conversions: Vec<Conversion>,
 }
 
-#[derive(Debug, Hash, PartialEq, Eq)]
-enum Type {
-    Seed,
-    Soil,
-    Fertilizer,
-    Water,
-    Light,
-    Temperature,
-    Humidity,
-    Location,
+/// A map from one category to another, like `seed-to-soil`.
+#[derive(Debug)]
+struct Conversion {
+    from: String,
+    to: String,
+    map: ConversionMap,
 }
 
-#[derive(Debug)]
-struct ConversionMap {
+impl Almanac {
+    /// The seeds read as pairs of a start and a length, sorted and merged.
+    pub fn seed_ranges(&self) -> Vec<Range<usize>> {
+        merge(
+            self.seeds
+                .chunks_exact(2)
+                .map(|pair| pair[0]..pair[0] + pair[1]),
+        )
+    }
+
+    /// The maps that lead from `source` to `target`, found by their category
+    /// names and composed into a single map. Fails unless there is exactly one
+    /// such chain of maps, and when the maps from `source` form a cycle.
+    pub fn chain(&self, source: &str, target: &str) -> Result<ConversionMap> {
+        let mut search = Search {
+            target,
+            path: vec![],
+            reached: vec![source],
+            chains: vec![],
+        };
+        self.search(source, &mut search)?;
+
+        match &search.chains[..] {
+            [chain] => Ok(chain
+                .iter()
+                .fold(ConversionMap::default(), |map, conversion| {
+                    map.then(&conversion.map)
+                })),
+            [] if search.reached.len() == 1 => bail!("there are no maps from {source}"),
+            [] => bail!(
+                "there are no maps from {source} to {target}, only to {}",
+                search.reached.iter().skip(1).join(", ")
+            ),
+            [a, b, ..] => bail!(
+                "there is more than one way from {source} to {target}: {} and {}",
+                describe_chain(source, a),
+                describe_chain(source, b)
+            ),
+        }
+    }
+
+    fn search<'a>(&'a self, category: &'a str, search: &mut Search<'a>) -> Result<()> {
+        if category == search.target {
+            search.chains.push(search.path.clone());
+            return Ok(());
+        }
+
+        for conversion in self.conversions.iter().filter(|c| c.from == category) {
+            let on_path = search
+                .path
+                .iter()
+                .map(|c| c.from.as_str())
+                .chain([category])
+                .collect_vec();
+            if let Some(start) = on_path.iter().position(|&name| name == conversion.to) {
+                let cycle = on_path[start..]
+                    .iter()
+                    .copied()
+                    .chain([conversion.to.as_str()])
+                    .join(" -> ");
+                bail!("the maps form a cycle: {cycle}");
+            }
+
+            if !search.reached.contains(&conversion.to.as_str()) {
+                search.reached.push(&conversion.to);
+            }
+
+            search.path.push(conversion);
+            self.search(&conversion.to, search)?;
+            search.path.pop();
+
+            if search.chains.len() > 1 {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The state of the depth-first search for chains of maps.
+struct Search<'a> {
+    target: &'a str,
+    path: Vec<&'a Conversion>,
+    /// Every category reached so far, starting with the source.
+    reached: Vec<&'a str>,
+    chains: Vec<Vec<&'a Conversion>>,
+}
+
+fn describe_chain(source: &str, chain: &[&Conversion]) -> String {
+    [source]
+        .into_iter()
+        .chain(chain.iter().map(|conversion| conversion.to.as_str()))
+        .join(" -> ")
+}
+
+/// Sorts `ranges` and joins the ones that overlap or touch.
+fn merge(ranges: impl IntoIterator<Item = Range<usize>>) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = vec![];
+
+    for range in ranges
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .sorted_by_key(|range| range.start)
+    {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// A piecewise map of values, with entries sorted by their source ranges,
+/// which never overlap. Values outside every entry map to themselves.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConversionMap {
     entries: Vec<MapEntry>,
 }
 
-#[derive(Debug)]
+/// Maps the half-open range `src..src + range` to `dest..dest + range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct MapEntry {
     dest: usize,
     src: usize,
     range: usize,
 }
 
-impl ConversionMap {
+impl MapEntry {
+    fn end(&self) -> usize {
+        self.src + self.range
+    }
+
     fn convert(&self, from: usize) -> usize {
-        if let Some(entry) = self
-            .entries
+        self.dest + (from - self.src)
+    }
+}
+
+/// Why a list of entries doesn't make a [`ConversionMap`].
+#[derive(Debug)]
+enum InvalidMap {
+    /// The indices of two entries whose source ranges overlap.
+    Overlap(usize, usize),
+    /// The index of an entry whose source or destination range goes past the
+    /// largest value.
+    TooLarge(usize),
+}
+
+impl ConversionMap {
+    /// Sorts the entries by source range, leaving out empty ones.
+    fn new(entries: &[MapEntry]) -> std::result::Result<Self, InvalidMap> {
+        if let Some(i) = entries.iter().position(|entry| {
+            entry.src.checked_add(entry.range).is_none()
+                || entry.dest.checked_add(entry.range).is_none()
+        }) {
+            return Err(InvalidMap::TooLarge(i));
+        }
+
+        let order = (0..entries.len())
+            .filter(|&i| entries[i].range > 0)
+            .sorted_by_key(|&i| entries[i].src)
+            .collect_vec();
+
+        if let Some((&a, &b)) = order
             .iter()
-            .find(|e| e.src <= from && from <= e.src + e.range)
+            .tuple_windows()
+            .find(|(&a, &b)| entries[a].end() > entries[b].src)
         {
-            entry.dest + (from - entry.src)
-        } else {
-            from
+            return Err(InvalidMap::Overlap(a, b));
+        }
+
+        let entries = order.into_iter().map(|i| entries[i]).collect();
+        Ok(Self { entries })
+    }
+
+    /// The entry whose source range contains `from`.
+    fn entry(&self, from: usize) -> Option<&MapEntry> {
+        let i = self.entries.partition_point(|entry| entry.src <= from);
+        self.entries[..i].last().filter(|entry| from < entry.end())
+    }
+
+    pub fn convert(&self, from: usize) -> usize {
+        self.entry(from).map_or(from, |entry| entry.convert(from))
+    }
+
+    /// Maps a whole range of values, splitting it where it crosses the edges
+    /// of the entries. The pieces are returned in the order of their sources.
+    pub fn convert_range(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        let first = self
+            .entries
+            .partition_point(|entry| entry.end() <= range.start);
+        let mut start = range.start;
+        let mut pieces = vec![];
+
+        for entry in &self.entries[first..] {
+            if entry.src >= range.end {
+                break;
+            }
+
+            if start < entry.src {
+                pieces.push(start..entry.src);
+                start = entry.src;
+            }
+
+            let end = range.end.min(entry.end());
+            pieces.push(entry.convert(start)..entry.convert(start) + (end - start));
+            start = end;
+        }
+
+        if start < range.end {
+            pieces.push(start..range.end);
         }
+
+        pieces
+    }
+
+    /// Every value that converts to one in `range`, as sorted ranges. Several
+    /// entries can map to the same values, so there can be more than one.
+    pub fn preimage(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        merge(self.segments().into_iter().filter_map(|segment| {
+            let start = range.start.max(segment.dest);
+            let end = range.end.min(segment.dest + segment.range);
+            (start < end)
+                .then(|| segment.src + (start - segment.dest)..segment.src + (end - segment.dest))
+        }))
+    }
+
+    /// A single map that does the same as converting with `self` and then
+    /// with `next`.
+    #[must_use]
+    pub fn then(&self, next: &ConversionMap) -> ConversionMap {
+        let mut entries = vec![];
+
+        for segment in self.segments() {
+            let mut src = segment.src;
+            for piece in next.convert_range(segment.dest..segment.dest + segment.range) {
+                let range = piece.len();
+                if piece.start != src {
+                    entries.push(MapEntry {
+                        dest: piece.start,
+                        src,
+                        range,
+                    });
+                }
+                src += range;
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Splits every value into consecutive ranges, each either covered by an
+    /// entry or mapped to itself.
+    fn segments(&self) -> Vec<MapEntry> {
+        let unmapped = |src: usize, end: usize| MapEntry {
+            dest: src,
+            src,
+            range: end - src,
+        };
+
+        let mut segments = vec![];
+        let mut next = 0;
+        for &entry in &self.entries {
+            if next < entry.src {
+                segments.push(unmapped(next, entry.src));
+            }
+            segments.push(entry);
+            next = entry.end();
+        }
+        if next < usize::MAX {
+            segments.push(unmapped(next, usize::MAX));
+        }
+
+        segments
+    }
+}
+
+/// Prints the entries in the order and format of the almanac.
+impl Display for ConversionMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self
+            .entries
+            .iter()
+            .map(|entry| format!("{} {} {}", entry.dest, entry.src, entry.range));
+        write!(f, "{}", lines.format("\n"))
     }
 }
 
 impl FromStr for Almanac {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match all_consuming(parse_almanac)(s).finish() {
-            Ok((_, almanac)) => Ok(almanac),
-            Err(err) => bail!("failed to parse almanac: {err}"),
+        let (seeds, raw_maps) = parse_all(s, parse_almanac)?;
+        let line_of = |text: &str| s[..s.offset(text)].matches('\n').count() + 1;
+
+        if let Some(i) = seeds
+            .chunks_exact(2)
+            .position(|pair| pair[0].checked_add(pair[1]).is_none())
+        {
+            // The seeds line is the label followed by the numbers.
+            let length = s.split_whitespace().nth(2 * i + 2).unwrap_or(s);
+            return Err(ParseError::at(s, s.offset(length), too_large()));
+        }
+
+        let mut conversions = vec![];
+        for (i, raw) in raw_maps.iter().enumerate() {
+            if let Some(first) = raw_maps[..i]
+                .iter()
+                .find(|first| (first.from, first.to) == (raw.from, raw.to))
+            {
+                let expected =
+                    format!("a map other than the one on line {}", line_of(first.header));
+                return Err(ParseError::at(s, s.offset(raw.header), expected));
+            }
+
+            let (lines, entries): (Vec<_>, Vec<_>) = raw.entries.iter().copied().unzip();
+            let map = ConversionMap::new(&entries).map_err(|err| match err {
+                InvalidMap::Overlap(a, b) => {
+                    let (first, second) = (a.min(b), a.max(b));
+                    let expected = format!(
+                        "a source range that doesn't overlap the one on line {}",
+                        line_of(lines[first])
+                    );
+                    ParseError::at(s, s.offset(lines[second]), expected)
+                }
+                InvalidMap::TooLarge(i) => {
+                    let range = lines[i].split_whitespace().nth(2).unwrap_or(lines[i]);
+                    ParseError::at(s, s.offset(range), too_large())
+                }
+            })?;
+
+            conversions.push(Conversion {
+                from: raw.from.to_string(),
+                to: raw.to.to_string(),
+                map,
+            });
         }
+
+        Ok(Almanac { seeds, conversions })
     }
 }
 
-fn parse_almanac(s: &str) -> IResult<&str, Almanac> {
-    let (s, (seeds, conversion_maps)) = separated_pair(
-        parse_seeds,
-        tag("\n\n"),
-        separated_list1(tag("\n\n"), parse_conversion_map),
-    )(s)?;
-    let (s, _) = opt(newline)(s)?;
-    let conversion_maps = conversion_maps.into_iter().collect();
-    Ok((
-        s,
-        Almanac {
-            seeds,
-            conversion_maps,
-        },
-    ))
+fn too_large() -> String {
+    format!("a length that ends the range by {}", usize::MAX)
+}
+
+/// A map as it was parsed, keeping the lines it came from to point at them
+/// in errors.
+struct RawMap<'a> {
+    header: &'a str,
+    from: &'a str,
+    to: &'a str,
+    entries: Vec<(&'a str, MapEntry)>,
 }
 
-fn parse_seeds(s: &str) -> IResult<&str, Vec<usize>> {
-    let (s, _) = tag("seeds: ")(s)?;
-    let (s, seeds) = separated_list1(space1, map_res(digit1, str::parse))(s)?;
-    Ok((s, seeds))
+fn parse_almanac(s: &str) -> IResult<&str, (Vec<usize>, Vec<RawMap<'_>>)> {
+    separated_pair(
+        labelled("seeds", numbers),
+        tag("\n\n"),
+        blocks(parse_conversion_map),
+    )(s)
 }
 
-fn parse_conversion_map(s: &str) -> IResult<&str, ((Type, Type), ConversionMap)> {
+fn parse_conversion_map(header: &str) -> IResult<&str, RawMap<'_>> {
     let (s, (from, _, to, _, entries)) = tuple((
-        map_opt(alpha1, get_map_type),
+        alpha1,
         tag("-to-"),
-        map_opt(alpha1, get_map_type),
+        alpha1,
         tag(" map:\n"),
-        separated_list1(newline, parse_map_entry),
-    ))(s)?;
-
-    Ok((s, ((from, to), ConversionMap { entries })))
-}
-
-fn parse_map_entry(s: &str) -> IResult<&str, MapEntry> {
-    let mut parse_digit = map_res(digit1, str::parse);
-    let (s, dest) = parse_digit(s)?;
-    let (s, _) = space1(s)?;
-    let (s, src) = parse_digit(s)?;
-    let (s, _) = space1(s)?;
-    let (s, range) = parse_digit(s)?;
+        lines(parse_map_entry),
+    ))(header)?;
 
-    Ok((s, MapEntry { dest, src, range }))
+    Ok((
+        s,
+        RawMap {
+            header,
+            from,
+            to,
+            entries,
+        },
+    ))
 }
 
-fn get_map_type(s: &str) -> Option<Type> {
-    let map_type = match s {
-        "seed" => Type::Seed,
-        "soil" => Type::Soil,
-        "fertilizer" => Type::Fertilizer,
-        "water" => Type::Water,
-        "light" => Type::Light,
-        "temperature" => Type::Temperature,
-        "humidity" => Type::Humidity,
-        "location" => Type::Location,
-        _ => return None,
-    };
+fn parse_map_entry(line: &str) -> IResult<&str, (&str, MapEntry)> {
+    let (s, (dest, src, range)) = tuple((
+        unsigned,
+        preceded(space1, unsigned),
+        preceded(space1, unsigned),
+    ))(line)?;
 
-    Some(map_type)
+    Ok((s, (line, MapEntry { dest, src, range })))
 }
@@ -1,94 +1,109 @@
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, newline, space1},
-    combinator::{all_consuming, map_res, opt},
-    multi::separated_list1,
-    sequence::tuple,
-    Finish, IResult,
+use anyhow::{Context, Result};
+use nom::{character::complete::newline, sequence::separated_pair};
+
+use crate::{
+    answer::Answer,
+    params::Params,
+    parsing::{labelled, numbers, parse_all, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
 };
 
-use crate::solution::Solution;
-
 pub struct Day {}
 
+inventory::submit!(Registration::new(6, "Wait For It", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let races: Races = input.parse()?;
+    type Parsed = Races;
 
-        let result = races
-            .times
-            .into_iter()
-            .zip(races.records)
-            .map(|(time, record)| {
-                (1..time)
-                    .map(|button_held| {
-                        let time_remaining = time - button_held;
-                        let boat_speed = button_held;
-
-                        time_remaining * boat_speed
-                    })
-                    .filter(|distance| *distance > record)
-                    .count()
-            })
-            .product::<usize>();
-
-        Ok(result.to_string())
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let races: Races = input.parse()?;
+    fn part1(&self, races: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let result = races.times.iter().zip(&races.records).try_fold(
+            1_u128,
+            |product, (&time, &record)| {
+                let ways = ways_to_win(time, record).context("the race is too long")?;
+                product
+                    .checked_mul(ways)
+                    .context("the product of the ways to win overflows")
+            },
+        )?;
+
+        Ok(result.into())
+    }
 
+    fn part2(&self, races: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let time = races
             .times
             .iter()
             .fold(String::new(), |acc, t| format!("{acc}{t}"))
-            .parse::<usize>()?;
+            .parse::<u128>()?;
         let record = races
             .records
             .iter()
             .fold(String::new(), |acc, r| format!("{acc}{r}"))
-            .parse::<usize>()?;
+            .parse::<u128>()?;
 
-        let result = (1..time)
-            .map(|button_held| {
-                let time_remaining = time - button_held;
-                let boat_speed = button_held;
+        let result = ways_to_win(time, record).context("the race is too long")?;
 
-                time_remaining * boat_speed
-            })
-            .filter(|distance| *distance > record)
-            .count();
+        Ok(result.into())
+    }
+}
 
-        Ok(result.to_string())
+/// The number of whole milliseconds the button can be held for to go further
+/// than `record` in a race of `time`, or `None` if the numbers are too large.
+///
+/// Holding for `h` goes `h * (time - h)`, so the winning holds lie strictly
+/// between the roots of `h² - time * h + record`. Those are found with an
+/// integer square root and then corrected, which keeps the answer exact where
+/// a float would round, and excludes holds that only tie with the record.
+pub fn ways_to_win(time: u128, record: u128) -> Option<u128> {
+    let square = time.checked_mul(time)?;
+    // A record this large can't be beaten, since no hold goes further than
+    // `time² / 4`.
+    let Some(discriminant) = record
+        .checked_mul(4)
+        .and_then(|four_records| square.checked_sub(four_records))
+    else {
+        return Some(0);
+    };
+
+    let beats = |hold: u128| hold * (time - hold) > record;
+
+    // Rounding the root down puts this at most one below the first winning
+    // hold.
+    let mut first = (time - discriminant.isqrt()) / 2;
+    while first <= time / 2 && !beats(first) {
+        first += 1;
     }
+
+    // Winning holds are symmetric around `time / 2`.
+    Some((time + 1).saturating_sub(2 * first))
 }
 
-struct Races {
-    times: Vec<usize>,
-    records: Vec<usize>,
+pub struct Races {
+    times: Vec<u128>,
+    records: Vec<u128>,
 }
 
 impl FromStr for Races {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match all_consuming(parse_races)(s).finish() {
-            Ok((_, races)) => Ok(races),
-            Err(err) => bail!("failed to parse races: '{err}'"),
-        }
+        parse_all(s, parse_races)
     }
 }
 
 fn parse_races(s: &str) -> IResult<&str, Races> {
-    let mut parse_digits = separated_list1(space1, map_res(digit1, str::parse::<usize>));
-    let (s, (_, _, times)) = tuple((tag("Time:"), space1, &mut parse_digits))(s)?;
-    let (s, _) = newline(s)?;
-    let (s, (_, _, records)) = tuple((tag("Distance:"), space1, &mut parse_digits))(s)?;
-    let (s, _) = opt(newline)(s)?;
+    let (s, (times, records)) = separated_pair(
+        labelled("Time", numbers),
+        newline,
+        labelled("Distance", numbers),
+    )(s)?;
 
     Ok((s, Races { times, records }))
 }
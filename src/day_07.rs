@@ -1,72 +1,78 @@
 use core::panic;
 use std::{collections::HashMap, str::FromStr};
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use itertools::Itertools;
 use nom::{
-    character::complete::{digit1, newline, one_of, space1},
-    combinator::{all_consuming, map_res, opt},
-    multi::separated_list1,
+    character::complete::{one_of, space1},
     sequence::{separated_pair, tuple},
-    Finish, IResult,
 };
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::Params,
+    parsing::{lines, parse_all, unsigned, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(7, "Camel Cards", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let mut game: GameNoJokers = input.parse()?;
-
-        game.hands_and_bids.sort_by_key(|(hand, _)| *hand);
-        game.hands_and_bids.reverse();
-
-        game.hands_and_bids.iter().for_each(|(h, _)| {
-            println!("Hand '{:?}' is '{:?}'", h.cards, h.hand_type());
-        });
-
-        let result =
-            game.hands_and_bids
-                .into_iter()
-                .enumerate()
-                .fold(0_usize, |total, (idx, (_, bid))| {
-                    let rank = idx + 1;
-                    let score = rank * bid;
-                    total + score
-                });
-
-        Ok(result.to_string())
+    type Parsed = Game;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let mut game: GameWithJokers = input.parse()?;
+    fn part1(&self, game: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let result = game.total_winnings();
 
-        game.hands_and_bids.sort_by_key(|(hand, _)| *hand);
-        game.hands_and_bids.reverse();
+        Ok(result.into())
+    }
 
-        let result =
-            game.hands_and_bids
-                .into_iter()
-                .enumerate()
-                .fold(0_usize, |total, (idx, (_, bid))| {
-                    let rank = idx + 1;
-                    let score = rank * bid;
-                    total + score
-                });
+    fn part2(&self, game: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let result = game.with_jokers().total_winnings();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 }
 
-#[derive(Debug)]
-struct GameNoJokers {
+#[derive(Debug, Clone)]
+pub struct Game {
     hands_and_bids: Vec<(Hand, usize)>,
 }
 
-#[derive(Debug)]
-struct GameWithJokers {
-    hands_and_bids: Vec<(Hand, usize)>,
+impl Game {
+    /// Treats every Jack as a Joker, which is the weakest card but counts as
+    /// whatever makes the strongest hand.
+    fn with_jokers(&self) -> Self {
+        let hands_and_bids = self
+            .hands_and_bids
+            .iter()
+            .map(|(hand, bid)| (hand.with_jokers(), *bid))
+            .collect();
+
+        Self { hands_and_bids }
+    }
+
+    fn total_winnings(&self) -> usize {
+        let mut hands_and_bids = self.hands_and_bids.clone();
+
+        hands_and_bids.sort_by_key(|(hand, _)| *hand);
+        hands_and_bids.reverse();
+
+        hands_and_bids
+            .into_iter()
+            .enumerate()
+            .fold(0_usize, |total, (idx, (_, bid))| {
+                let rank = idx + 1;
+                let score = rank * bid;
+                total + score
+            })
+    }
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -103,87 +109,47 @@ enum HandType {
     HighCard,
 }
 
-impl FromStr for GameNoJokers {
-    type Err = anyhow::Error;
+impl FromStr for Game {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match all_consuming(parse_hands_and_bids(false))(s).finish() {
-            Ok((_, hands_and_bids)) => Ok(Self { hands_and_bids }),
-            Err(err) => bail!("failed to parse game: {err}"),
-        }
-    }
-}
+        let hands_and_bids = parse_all(s, parse_hands_and_bids)?;
 
-impl FromStr for GameWithJokers {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match all_consuming(parse_hands_and_bids(true))(s).finish() {
-            Ok((_, hands_and_bids)) => Ok(Self { hands_and_bids }),
-            Err(err) => bail!("failed to parse game: {err}"),
-        }
+        Ok(Self { hands_and_bids })
     }
 }
 
-fn parse_hands_and_bids(use_jokers: bool) -> impl Fn(&str) -> IResult<&str, Vec<(Hand, usize)>> {
-    move |s: &str| {
-        let parse_hand = parse_hand(use_jokers);
-        let (s, hands_and_bids) = separated_list1(
-            newline,
-            separated_pair(parse_hand, space1, map_res(digit1, str::parse::<usize>)),
-        )(s)?;
-        let (s, _) = opt(newline)(s)?;
-
-        Ok((s, hands_and_bids))
-    }
+fn parse_hands_and_bids(s: &str) -> IResult<&str, Vec<(Hand, usize)>> {
+    lines(separated_pair(parse_hand, space1, unsigned))(s)
 }
 
-fn parse_hand(use_jokers: bool) -> impl Fn(&str) -> IResult<&str, Hand> {
-    move |s: &str| {
-        let parse_card = parse_card(use_jokers);
-        let (s, cards) = tuple((
-            &parse_card,
-            &parse_card,
-            &parse_card,
-            &parse_card,
-            &parse_card,
-        ))(s)?;
-        Ok((s, Hand { cards }))
-    }
+fn parse_hand(s: &str) -> IResult<&str, Hand> {
+    let (s, cards) = tuple((parse_card, parse_card, parse_card, parse_card, parse_card))(s)?;
+    Ok((s, Hand { cards }))
 }
 
-fn parse_card(use_jokers: bool) -> impl Fn(&str) -> IResult<&str, Card> {
-    move |s: &str| {
-        use Card::{
-            Ace, Eight, Five, Four, Jack, Joker, King, Nine, Queen, Seven, Six, Ten, Three, Two,
-        };
-
-        let (s, card) = one_of("AKQJT98765432")(s)?;
-        let card = match card {
-            'A' => Ace,
-            'K' => King,
-            'Q' => Queen,
-            'J' => {
-                if use_jokers {
-                    Joker
-                } else {
-                    Jack
-                }
-            }
-            'T' => Ten,
-            '9' => Nine,
-            '8' => Eight,
-            '7' => Seven,
-            '6' => Six,
-            '5' => Five,
-            '4' => Four,
-            '3' => Three,
-            '2' => Two,
-            _ => panic!("this can't happen"),
-        };
-
-        Ok((s, card))
-    }
+fn parse_card(s: &str) -> IResult<&str, Card> {
+    use Card::{Ace, Eight, Five, Four, Jack, King, Nine, Queen, Seven, Six, Ten, Three, Two};
+
+    let (s, card) = one_of("AKQJT98765432")(s)?;
+    let card = match card {
+        'A' => Ace,
+        'K' => King,
+        'Q' => Queen,
+        'J' => Jack,
+        'T' => Ten,
+        '9' => Nine,
+        '8' => Eight,
+        '7' => Seven,
+        '6' => Six,
+        '5' => Five,
+        '4' => Four,
+        '3' => Three,
+        '2' => Two,
+        _ => panic!("this can't happen"),
+    };
+
+    Ok((s, card))
 }
 
 impl PartialOrd for Hand {
@@ -208,6 +174,21 @@ impl Ord for Hand {
 }
 
 impl Hand {
+    fn with_jokers(self) -> Self {
+        let joker = |card| {
+            if card == Card::Jack {
+                Card::Joker
+            } else {
+                card
+            }
+        };
+        let (c0, c1, c2, c3, c4) = self.cards;
+
+        Self {
+            cards: (joker(c0), joker(c1), joker(c2), joker(c3), joker(c4)),
+        }
+    }
+
     fn hand_type(self) -> HandType {
         use HandType::{
             FiveOfAKind, FourOfAKind, FullHouse, HighCard, OnePair, ThreeOfAKind, TwoPairs,
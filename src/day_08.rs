@@ -1,37 +1,34 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
-use anyhow::{bail, Context, Result};
-use itertools::Itertools;
+use anyhow::Result;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, newline},
-    combinator::{all_consuming, opt},
-    multi::separated_list1,
+    character::complete::{alphanumeric1, one_of},
+    combinator::map,
+    multi::many1,
     sequence::{delimited, separated_pair},
-    Finish, IResult,
 };
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::Params,
+    parsing::{lines, parse_all, tag, IResult, ParseError},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(8, "Haunted Wasteland", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let (input_instr, input_graph) =
-            input.split_once("\n\n").context("could not split input")?;
-
-        let instrs = input_instr
-            .chars()
-            .filter_map(|c| match c {
-                'L' => Some(Instr::Left),
-                'R' => Some(Instr::Right),
-                _ => None,
-            })
-            .collect_vec();
-
-        let Ok((_, graph)) = all_consuming(parse_graph)(input_graph).finish() else {
-            bail!("failed to parse graph")
-        };
+    type Parsed = Network;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, network: &Self::Parsed, _params: &Params) -> Result<Answer> {
+        let Network { instrs, graph } = network;
 
         let mut current_node = "AAA";
         let mut current_instr = 0;
@@ -41,8 +38,8 @@ impl Solution for Day {
             let instr = &instrs[current_instr];
 
             current_node = match instr {
-                Instr::Left => graph[current_node].0,
-                Instr::Right => graph[current_node].1,
+                Instr::Left => &graph[current_node].0,
+                Instr::Right => &graph[current_node].1,
             };
 
             current_instr += 1;
@@ -51,22 +48,48 @@ impl Solution for Day {
             total_steps += 1;
         }
 
-        Ok(total_steps.to_string())
+        Ok(total_steps.into())
     }
 
-    fn compute_2(&self, _input: &str) -> Result<String> {
+    fn part2(&self, _network: &Self::Parsed, _params: &Params) -> Result<Answer> {
         todo!()
     }
 }
 
+pub struct Network {
+    instrs: Vec<Instr>,
+    graph: HashMap<String, (String, String)>,
+}
+
 enum Instr {
     Left,
     Right,
 }
 
+impl FromStr for Network {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (instrs, graph) = parse_all(s, separated_pair(parse_instrs, tag("\n\n"), parse_graph))?;
+
+        let graph = graph
+            .into_iter()
+            .map(|(node, (left, right))| (node.to_string(), (left.to_string(), right.to_string())))
+            .collect();
+
+        Ok(Self { instrs, graph })
+    }
+}
+
+fn parse_instrs(s: &str) -> IResult<&str, Vec<Instr>> {
+    many1(map(one_of("LR"), |c| match c {
+        'L' => Instr::Left,
+        _ => Instr::Right,
+    }))(s)
+}
+
 fn parse_graph(s: &str) -> IResult<&str, HashMap<&str, (&str, &str)>> {
-    let (s, nodes) = separated_list1(newline, parse_node)(s)?;
-    let (s, _) = opt(newline)(s)?;
+    let (s, nodes) = lines(parse_node)(s)?;
 
     Ok((s, nodes.into_iter().collect()))
 }
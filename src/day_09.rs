@@ -1,25 +1,30 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use itertools::Itertools;
 
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    params::Params,
+    parsing::{lines, list, parse_all, signed},
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(9, "Mirage Maintenance", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let oasis_report: Vec<Vec<i64>> = input
-            .lines()
-            .map(|line| {
-                line.split(' ')
-                    .map(|n| n.parse().context(format!("failed to parse {n}")))
-                    .collect()
-            })
-            .collect::<Result<_>>()?;
+    type Parsed = Vec<Vec<i64>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(parse_all(input, lines(list(signed)))?)
+    }
 
+    fn part1(&self, oasis_report: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let result = oasis_report
-            .into_iter()
+            .iter()
             .map(|line| {
-                let mut series = vec![line];
+                let mut series = vec![line.clone()];
 
                 loop {
                     let s = series.last().unwrap();
@@ -38,23 +43,14 @@ impl Solution for Day {
             })
             .sum::<i64>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let oasis_report: Vec<Vec<i64>> = input
-            .lines()
-            .map(|line| {
-                line.split(' ')
-                    .map(|n| n.parse().context(format!("failed to parse {n}")))
-                    .collect()
-            })
-            .collect::<Result<_>>()?;
-
+    fn part2(&self, oasis_report: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let result = oasis_report
-            .into_iter()
+            .iter()
             .map(|line| {
-                let mut series = vec![line];
+                let mut series = vec![line.clone()];
 
                 loop {
                     let s = series.last().unwrap();
@@ -73,6 +69,6 @@ impl Solution for Day {
             })
             .sum::<i64>();
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 }
@@ -1,37 +1,55 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, num::NonZeroUsize, str::FromStr};
 
-use anyhow::{bail, Result};
+use anyhow::{Context, Result};
 
-use ndarray::Array2;
-
-use crate::solution::Solution;
+use crate::{
+    answer::Answer,
+    geometry::UPoint,
+    grid::Grid,
+    params::{Param, Params},
+    parsing::ParseError,
+    registry::Registration,
+    solution::Solution,
+};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(11, "Cosmic Expansion", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
-        let universe: Universe = input.parse()?;
+    type Parsed = Universe;
+
+    const PARAMS: &'static [Param] = &[Param::new::<NonZeroUsize>(
+        "factor",
+        "1000000",
+        "how many times larger empty space becomes in part 2",
+    )];
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, universe: &Self::Parsed, _params: &Params) -> Result<Answer> {
         let universe = universe.expand();
         let galaxies = universe.get_galaxies();
 
         let mut result = 0_usize;
         for i in 0..galaxies.len() - 1 {
             for j in (i + 1)..galaxies.len() {
-                result += galaxies[i].distance(&galaxies[j]);
+                result += galaxies[i].manhattan_distance(&galaxies[j]);
             }
         }
 
-        Ok(result.to_string())
+        Ok(result.into())
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
-        let universe: Universe = input.parse()?;
+    fn part2(&self, universe: &Self::Parsed, params: &Params) -> Result<Answer> {
         let galaxies = universe.get_galaxies();
 
         let mut before_expansion = 0_usize;
         for i in 0..galaxies.len() - 1 {
             for j in (i + 1)..galaxies.len() {
-                before_expansion += galaxies[i].distance(&galaxies[j]);
+                before_expansion += galaxies[i].manhattan_distance(&galaxies[j]);
             }
         }
 
@@ -41,35 +59,25 @@ impl Solution for Day {
         let mut after_expansion = 0_usize;
         for i in 0..galaxies.len() - 1 {
             for j in (i + 1)..galaxies.len() {
-                after_expansion += galaxies[i].distance(&galaxies[j]);
+                after_expansion += galaxies[i].manhattan_distance(&galaxies[j]);
             }
         }
 
         let one_expansion_diff = after_expansion - before_expansion;
 
-        let result = before_expansion + (one_expansion_diff * 999_999);
-
-        Ok(result.to_string())
-    }
-}
+        let factor = params.get::<NonZeroUsize>("factor")?.get();
 
-struct Universe {
-    universe: Array2<Space>,
-}
+        let result = one_expansion_diff
+            .checked_mul(factor - 1)
+            .and_then(|expansion| expansion.checked_add(before_expansion))
+            .context("the expansion factor is too large")?;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-struct Point {
-    x: usize,
-    y: usize,
+        Ok(result.into())
+    }
 }
 
-impl Point {
-    fn distance(&self, other: &Point) -> usize {
-        let x = self.x.abs_diff(other.x);
-        let y = self.y.abs_diff(other.y);
-
-        x + y
-    }
+pub struct Universe {
+    universe: Grid<Space>,
 }
 
 #[derive(Clone, Copy)]
@@ -79,89 +87,61 @@ enum Space {
 }
 
 impl Universe {
-    fn expand(self) -> Self {
-        let mut rows = Vec::with_capacity(self.universe.shape()[0]);
-
-        for row in self.universe.rows() {
-            if row.iter().all(|space| matches!(space, Space::Empty)) {
-                rows.push(vec![Space::Empty; row.len()]);
-            }
-            rows.push(row.to_vec());
-        }
-
-        let universe = Array2::from_shape_fn((rows.len(), rows[0].len()), |(y, x)| rows[y][x]);
-
-        let mut columns = Vec::with_capacity(self.universe.shape()[1]);
-
-        for column in universe.columns() {
-            if column.iter().all(|space| matches!(space, Space::Empty)) {
-                columns.push(vec![Space::Empty; column.len()]);
-            }
-            columns.push(column.to_vec());
-        }
-
-        let universe =
-            Array2::from_shape_fn((columns[0].len(), columns.len()), |(y, x)| columns[x][y]);
+    /// Doubles every empty row and column.
+    fn expand(&self) -> Self {
+        let universe = expand_rows(&self.universe).transpose();
+        let universe = expand_rows(&universe).transpose();
 
         Self { universe }
     }
 
-    fn get_galaxies(&self) -> Vec<Point> {
-        let mut galaxies = vec![];
-        let shape = self.universe.shape();
+    fn get_galaxies(&self) -> Vec<UPoint> {
+        self.universe
+            .iter()
+            .filter(|(_, space)| matches!(space, Space::Galaxy))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
 
-        for y in 0..shape[0] {
-            for x in 0..shape[1] {
-                let space = self.universe.get((y, x)).unwrap();
-                if matches!(space, Space::Galaxy) {
-                    galaxies.push(Point { x, y });
-                }
-            }
-        }
+fn expand_rows(universe: &Grid<Space>) -> Grid<Space> {
+    let mut rows = Vec::with_capacity(universe.height());
 
-        galaxies
+    for row in universe.rows() {
+        if row.iter().all(|space| matches!(space, Space::Empty)) {
+            rows.push(row.to_vec());
+        }
+        rows.push(row.to_vec());
     }
+
+    Grid::from_rows(rows)
 }
 
 impl FromStr for Universe {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let u_vec: Vec<Vec<_>> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        '#' => Ok(Space::Galaxy),
-                        '.' => Ok(Space::Empty),
-                        _ => bail!("Unknown space type '{c}'"),
-                    })
-                    .collect()
-            })
-            .collect::<Result<_>>()?;
-
-        let universe = Array2::from_shape_fn((u_vec.len(), u_vec[0].len()), |(y, x)| u_vec[y][x]);
+        let universe = Grid::parse(s, "'#' or '.'", |c| match c {
+            '#' => Some(Space::Galaxy),
+            '.' => Some(Space::Empty),
+            _ => None,
+        })?;
 
         Ok(Self { universe })
     }
 }
 
-impl Display for Universe {
+impl Display for Space {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.universe.rows() {
-            row.iter().for_each(|space| {
-                let _ = write!(
-                    f,
-                    "{}",
-                    match space {
-                        Space::Empty => ".",
-                        Space::Galaxy => "#",
-                    }
-                );
-            });
-            let _ = writeln!(f);
+        match self {
+            Space::Empty => write!(f, "."),
+            Space::Galaxy => write!(f, "#"),
         }
+    }
+}
 
-        Ok(())
+impl Display for Universe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.universe)
     }
 }
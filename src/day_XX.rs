@@ -1,15 +1,23 @@
 use anyhow::Result;
 
-use crate::solution::Solution;
+use crate::{answer::Answer, params::Params, registry::Registration, solution::Solution};
 
 pub struct Day {}
 
+inventory::submit!(Registration::new(XX, "TITLE", &Day {}));
+
 impl Solution for Day {
-    fn compute_1(&self, input: &str) -> Result<String> {
+    type Parsed = String;
+
+    fn parse(&self, _input: &str) -> Result<Self::Parsed> {
+        todo!()
+    }
+
+    fn part1(&self, _parsed: &Self::Parsed, _params: &Params) -> Result<Answer> {
         todo!()
     }
 
-    fn compute_2(&self, input: &str) -> Result<String> {
+    fn part2(&self, _parsed: &Self::Parsed, _params: &Params) -> Result<Answer> {
         todo!()
     }
 }
@@ -0,0 +1,252 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A point or vector in 2D, with `y` growing downwards like the rows of an
+/// input. Points compare row by row, in reading order.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct Point<T> {
+    pub y: T,
+    pub x: T,
+}
+
+/// A point that can't be negative, like a position in a grid.
+pub type UPoint = Point<usize>;
+
+/// A point or vector that can be negative.
+pub type IPoint = Point<i64>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { y, x }
+    }
+}
+
+impl UPoint {
+    /// Moves by `vector`, or `None` if that would leave the unsigned range.
+    pub fn checked_add(self, vector: IPoint) -> Option<Self> {
+        Some(Self {
+            y: self.y.checked_add_signed(isize::try_from(vector.y).ok()?)?,
+            x: self.x.checked_add_signed(isize::try_from(vector.x).ok()?)?,
+        })
+    }
+}
+
+impl From<UPoint> for IPoint {
+    fn from(point: UPoint) -> Self {
+        let convert = |n: usize| i64::try_from(n).expect("coordinate fits in an i64");
+        Self::new(convert(point.x), convert(point.y))
+    }
+}
+
+impl TryFrom<IPoint> for UPoint {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: IPoint) -> Result<Self, Self::Error> {
+        Ok(Self::new(point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+macro_rules! impl_distances {
+    ($($t:ty => $distance:ty),*) => {
+        $(
+            impl Point<$t> {
+                /// The distance when moving only horizontally and vertically.
+                #[allow(dead_code)]
+                pub fn manhattan_distance(&self, other: &Self) -> $distance {
+                    self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+                }
+
+                /// The distance when diagonal moves are allowed too.
+                #[allow(dead_code)]
+                pub fn chebyshev_distance(&self, other: &Self) -> $distance {
+                    self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+                }
+            }
+        )*
+    };
+}
+
+impl_distances!(usize => usize, i64 => u64);
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four cardinal directions, in clockwise order.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// A step of one in this direction.
+    pub fn vector(self) -> IPoint {
+        match self {
+            Direction::Up => IPoint::new(0, -1),
+            Direction::Right => IPoint::new(1, 0),
+            Direction::Down => IPoint::new(0, 1),
+            Direction::Left => IPoint::new(-1, 0),
+        }
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the eight cardinal and diagonal directions, in clockwise order.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// A step of one in this direction, diagonals moving along both axes.
+    pub fn vector(self) -> IPoint {
+        match self {
+            Direction8::Up => IPoint::new(0, -1),
+            Direction8::UpRight => IPoint::new(1, -1),
+            Direction8::Right => IPoint::new(1, 0),
+            Direction8::DownRight => IPoint::new(1, 1),
+            Direction8::Down => IPoint::new(0, 1),
+            Direction8::DownLeft => IPoint::new(-1, 1),
+            Direction8::Left => IPoint::new(-1, 0),
+            Direction8::UpLeft => IPoint::new(-1, -1),
+        }
+    }
+
+    /// Turns 45 degrees clockwise.
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45 degrees counterclockwise.
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+/// Twice the area enclosed by a polygon with the given corners, using the
+/// shoelace formula. Doubling keeps the result an integer for any points.
+#[allow(dead_code)]
+pub fn double_area(vertices: &[IPoint]) -> u64 {
+    let sum: i64 = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum();
+
+    sum.unsigned_abs()
+}
+
+/// The number of integer points on the edges of a polygon with the given
+/// corners.
+#[allow(dead_code)]
+pub fn boundary_points(vertices: &[IPoint]) -> u64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+        .sum()
+}
+
+/// The number of integer points strictly inside a polygon, from its doubled
+/// area and boundary points using Pick's theorem.
+#[allow(dead_code)]
+pub fn interior_points(double_area: u64, boundary_points: u64) -> u64 {
+    (double_area + 2).saturating_sub(boundary_points) / 2
+}
+
+#[allow(dead_code)]
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
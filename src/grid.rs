@@ -0,0 +1,214 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    geometry::{Direction, Direction8, IPoint, UPoint},
+    parsing::ParseError,
+};
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(UPoint) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| UPoint::new(x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for row in rows {
+            assert_eq!(
+                *width.get_or_insert(row.len()),
+                row.len(),
+                "rows of a grid must have the same length"
+            );
+            cells.extend(row);
+            height += 1;
+        }
+
+        Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with one cell per character. `cell` returns `None` for
+    /// characters that aren't allowed, which are reported as `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut rows = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| cell(c).ok_or_else(|| ParseError::at(line, i, expected).below(y)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let width = *width.get_or_insert(row.len());
+            if row.len() != width {
+                let offset = line
+                    .char_indices()
+                    .nth(width)
+                    .map_or(line.len(), |(i, _)| i);
+                let expected = format!("a row of {width} cells");
+                return Err(ParseError::at(line, offset, expected).below(y));
+            }
+
+            rows.push(row);
+        }
+
+        Ok(Self::from_rows(rows))
+    }
+
+    #[allow(dead_code)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: UPoint) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: UPoint) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: UPoint) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.y * self.width + pos.x])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = UPoint> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| UPoint::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (UPoint, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The horizontal and vertical neighbours of `pos` inside the grid.
+    #[allow(dead_code)]
+    pub fn neighbours4(&self, pos: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+        self.neighbours(pos, Direction::ALL.map(Direction::vector))
+    }
+
+    /// The neighbours of `pos` inside the grid, diagonals included.
+    pub fn neighbours8(&self, pos: UPoint) -> impl Iterator<Item = UPoint> + '_ {
+        self.neighbours(pos, Direction8::ALL.map(Direction8::vector))
+    }
+
+    fn neighbours<const N: usize>(
+        &self,
+        pos: UPoint,
+        vectors: [IPoint; N],
+    ) -> impl Iterator<Item = UPoint> + '_ {
+        vectors
+            .into_iter()
+            .filter_map(move |vector| pos.checked_add(vector))
+            .filter(|&pos| self.contains(pos))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, and such a grid has no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[allow(dead_code)]
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    #[allow(dead_code)]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[UPoint::new(pos.y, pos.x)].clone()
+        })
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[UPoint::new(pos.y, self.height - 1 - pos.x)].clone()
+        })
+    }
+
+    #[allow(dead_code)]
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[UPoint::new(self.width - 1 - pos.y, pos.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<UPoint> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: UPoint) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<UPoint> for Grid<T> {
+    fn index_mut(&mut self, pos: UPoint) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+/// Prints one line per row, with the cells next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self.rows().map(|row| row.iter().join(""));
+        write!(f, "{}", rows.format("\n"))
+    }
+}
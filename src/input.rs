@@ -0,0 +1,134 @@
+use std::{
+    fs::read_to_string,
+    io::{read_to_string as read_all, stdin},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+
+use crate::solution::DynSolution;
+
+/// Where the puzzle input for a run comes from.
+pub enum InputSource {
+    /// The personal input for a day, stored in the crate's `input` directory.
+    Day(String),
+    /// A named example input for a day, stored in `input/examples/{day}`.
+    Example {
+        day: String,
+        name: String,
+    },
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    /// Resolves the CLI options into a source, falling back to the input for
+    /// `day` when none is given. A `path` of `-` means stdin.
+    pub fn from_args(
+        day: &str,
+        path: Option<&str>,
+        text: Option<&str>,
+        example: Option<&str>,
+    ) -> Result<Self> {
+        let source = match (path, text, example) {
+            (Some("-"), None, None) => Self::Stdin,
+            (Some(path), None, None) => Self::File(path.into()),
+            (None, Some(text), None) => Self::Text(text.to_string()),
+            (None, None, Some(name)) => Self::Example {
+                day: day.to_string(),
+                name: name.to_string(),
+            },
+            (None, None, None) => Self::Day(day.to_string()),
+            _ => bail!("only one of --input, --text and --example can be used"),
+        };
+
+        Ok(source)
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Self::Day(day) => read_file(&input_dir().join(day)),
+            Self::Example { day, name } => {
+                read_file(&input_dir().join("examples").join(day).join(name))
+            }
+            Self::File(path) => read_file(path),
+            Self::Stdin => read_all(stdin()).context("Failed to read stdin"),
+            Self::Text(text) => Ok(text.clone()),
+        }
+    }
+
+    /// Reads the input and normalises it for `solution`, warning on stderr
+    /// when that changed anything.
+    pub fn load(&self, solution: &dyn DynSolution) -> Result<String> {
+        let (input, changes) = normalise(&self.read()?, solution.trims_whitespace());
+
+        if !changes.is_empty() {
+            eprintln!("warning: normalised {self}: {}", changes.join(", "));
+        }
+
+        Ok(input)
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Day(day) => write!(f, "the input for day {day}"),
+            Self::Example { day, name } => write!(f, "example {name} for day {day}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Text(_) => write!(f, "the --text input"),
+        }
+    }
+}
+
+/// Removes a byte order mark and turns CRLF line endings into LF, which
+/// editors on some platforms add when saving an input. With `trim_whitespace`
+/// it also removes whitespace at the end of lines and blank lines at the end,
+/// keeping the final newline if there was one. Returns the normalised input
+/// and a description of each change that was made.
+pub fn normalise(input: &str, trim_whitespace: bool) -> (String, Vec<&'static str>) {
+    let mut changes = vec![];
+
+    let input = match input.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            changes.push("removed a byte order mark");
+            rest
+        }
+        None => input,
+    };
+
+    let mut input = if input.contains("\r\n") {
+        changes.push("converted CRLF line endings");
+        input.replace("\r\n", "\n")
+    } else {
+        input.to_string()
+    };
+
+    if trim_whitespace {
+        let mut trimmed = input.lines().map(str::trim_end).join("\n");
+        trimmed.truncate(trimmed.trim_end_matches('\n').len());
+        if input.ends_with('\n') && !trimmed.is_empty() {
+            trimmed.push('\n');
+        }
+
+        if trimmed != input {
+            changes.push("removed trailing whitespace");
+            input = trimmed;
+        }
+    }
+
+    (input, changes)
+}
+
+/// The `input` directory next to the crate manifest, so the binary finds the
+/// inputs regardless of the working directory it is started from.
+fn input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    read_to_string(path).context(format!("Failed to read {}", path.display()))
+}
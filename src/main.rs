@@ -1,3 +1,7 @@
+mod answer;
+mod answers;
+mod bench;
+mod cancel;
 mod day_01;
 mod day_02;
 mod day_03;
@@ -8,61 +12,403 @@ mod day_07;
 mod day_08;
 mod day_09;
 mod day_11;
+mod geometry;
+mod grid;
+mod input;
+mod memory;
+mod params;
+mod parsing;
+mod registry;
+mod report;
+mod runner;
+mod scaffold;
 mod solution;
+#[cfg(test)]
 mod tests;
+mod verify;
 
-use std::fs::read_to_string;
+use std::time::Duration;
 
-use anyhow::{bail, Context};
+use anyhow::bail;
 use argh::FromArgs;
-use solution::Solution;
+use input::InputSource;
+use params::{parse_assignment, Params};
+use registry::Registration;
+use report::Format;
+use runner::{Part, Status};
 
 #[derive(FromArgs)]
 /// Advent of Code 2023
 struct Args {
+    #[argh(subcommand)]
+    command: Command,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Run(RunArgs),
+    All(AllArgs),
+    Bench(BenchArgs),
+    List(ListArgs),
+    Verify(VerifyArgs),
+    New(NewArgs),
+}
+
+#[derive(FromArgs)]
+/// Run a day, either a single part or both parts sharing one parse
+#[argh(subcommand, name = "run")]
+struct RunArgs {
+    #[argh(positional)]
+    day: String,
+
+    /// part to run: 01, 02 or both (the default)
+    #[argh(positional)]
+    part: Option<String>,
+
+    /// read the input from this file instead, or from stdin if `-`
+    #[argh(option)]
+    input: Option<String>,
+
+    /// use this text as the input
+    #[argh(option)]
+    text: Option<String>,
+
+    /// use the named example from input/examples/<day> as the input
+    #[argh(option)]
+    example: Option<String>,
+
+    /// set a parameter of the day, like factor=10, see `list` for each day's
+    /// parameters
+    #[argh(option, from_str_fn(parse_assignment))]
+    param: Vec<(String, String)>,
+
+    /// output format: text, json or csv
+    #[argh(option, default = "Format::Text")]
+    format: Format,
+
+    /// stop a solution call that runs longer than this, like 30s or 500ms
+    #[argh(option, from_str_fn(parse_duration))]
+    timeout: Option<Duration>,
+
+    /// stop a solution part that allocates more than this, like 512M or 2G
+    /// (needs the `memory` feature)
+    #[argh(option, from_str_fn(parse_size))]
+    memory_limit: Option<usize>,
+}
+
+#[derive(FromArgs)]
+/// Run both parts of every day and print a table of the results
+#[argh(subcommand, name = "all")]
+struct AllArgs {
+    /// output format: text, json or csv
+    #[argh(option, default = "Format::Text")]
+    format: Format,
+
+    /// stop a solution call that runs longer than this, like 30s or 500ms
+    #[argh(option, from_str_fn(parse_duration))]
+    timeout: Option<Duration>,
+
+    /// stop a solution part that allocates more than this, like 512M or 2G
+    /// (needs the `memory` feature)
+    #[argh(option, from_str_fn(parse_size))]
+    memory_limit: Option<usize>,
+}
+
+#[derive(FromArgs)]
+/// Run a single part of a day repeatedly and report timing statistics
+#[argh(subcommand, name = "bench")]
+struct BenchArgs {
     #[argh(positional)]
     day: String,
 
     #[argh(positional)]
     part: String,
+
+    /// number of measured runs
+    #[argh(option, default = "100")]
+    runs: usize,
+
+    /// number of unmeasured runs before measuring
+    #[argh(option, default = "10")]
+    warmup: usize,
+
+    /// read the input from this file instead, or from stdin if `-`
+    #[argh(option)]
+    input: Option<String>,
+
+    /// use this text as the input
+    #[argh(option)]
+    text: Option<String>,
+
+    /// use the named example from input/examples/<day> as the input
+    #[argh(option)]
+    example: Option<String>,
+
+    /// set a parameter of the day, like factor=10, see `list` for each day's
+    /// parameters
+    #[argh(option, from_str_fn(parse_assignment))]
+    param: Vec<(String, String)>,
+}
+
+#[derive(FromArgs)]
+/// List every registered day
+#[argh(subcommand, name = "list")]
+struct ListArgs {}
+
+#[derive(FromArgs)]
+/// Run every day and compare the answers with answers.toml
+#[argh(subcommand, name = "verify")]
+struct VerifyArgs {
+    /// write answers for parts that have none recorded yet to answers.toml
+    #[argh(switch)]
+    record: bool,
+
+    /// stop a solution call that runs longer than this, like 30s or 500ms
+    #[argh(option, from_str_fn(parse_duration))]
+    timeout: Option<Duration>,
+
+    /// stop a solution part that allocates more than this, like 512M or 2G
+    /// (needs the `memory` feature)
+    #[argh(option, from_str_fn(parse_size))]
+    memory_limit: Option<usize>,
+}
+
+#[derive(FromArgs)]
+/// Create a new day from the day template
+#[argh(subcommand, name = "new")]
+struct NewArgs {
+    #[argh(positional)]
+    day: String,
+
+    /// title of the puzzle
+    #[argh(option, default = "String::from(\"TITLE\")")]
+    title: String,
 }
 
 fn main() -> anyhow::Result<()> {
     let args: Args = argh::from_env();
-    let Args { day, part } = args;
 
-    let file = format!("./input/{}", day.as_str());
-    let input = read_to_string(&file).context(format!("Failed to read {file}"))?;
+    runner::install_panic_hook();
+    registry::days()?;
 
-    if let Some(day) = get_day(day.as_str()) {
-        let answer = match part.as_str() {
-            "01" => day.compute_1(input.as_str())?,
-            "02" => day.compute_2(input.as_str())?,
-            _ => bail!("Part {part} was not found"),
-        };
+    match args.command {
+        Command::Run(args) => run(&args),
+        Command::All(args) => all(&args),
+        Command::Bench(args) => bench(&args),
+        Command::List(_) => list(),
+        Command::Verify(args) => {
+            memory::set_limit(args.memory_limit)?;
+            verify::verify(args.record, args.timeout)
+        }
+        Command::New(args) => scaffold::new_day(&args.day, &args.title),
+    }
+}
+
+fn run(args: &RunArgs) -> anyhow::Result<()> {
+    let RunArgs {
+        day,
+        part,
+        input,
+        text,
+        example,
+        param,
+        format,
+        timeout,
+        memory_limit,
+    } = args;
+
+    memory::set_limit(*memory_limit)?;
+
+    let registration = find_day(day)?;
+    let day = registration.id();
+    let input =
+        InputSource::from_args(&day, input.as_deref(), text.as_deref(), example.as_deref())?
+            .load(registration.solution)?;
+    let parts = Part::parse_selection(part.as_deref())?;
+    let params = Params::new(
+        registration.solution.params(),
+        &param.iter().cloned().collect(),
+    )?;
+
+    let mut outcomes = runner::run_parts(
+        &day,
+        registration.solution,
+        &parts,
+        &input,
+        &params,
+        *timeout,
+    );
+
+    if *format != Format::Text || outcomes.len() > 1 {
+        report::print_outcomes(&outcomes, *format)?;
+        return check_outcomes(&outcomes);
+    }
+
+    let outcome = outcomes.remove(0);
+    let part = outcome.part;
+
+    match outcome.status {
+        Status::Ok(answer) => println!("The answer is:\n{answer}"),
+        Status::Error(err) => return Err(err),
+        Status::NotImplemented => println!("Day {day} part {part} is not implemented yet"),
+        Status::Panicked(panic) => bail!("Day {day} part {part} {panic}"),
+        Status::TimedOut(timeout) => bail!("Day {day} part {part} timed out after {timeout:?}"),
+        Status::MemoryExceeded(limit) => bail!(
+            "Day {day} part {part} used more than {} of memory",
+            report::format_bytes(limit)
+        ),
+    }
+
+    Ok(())
+}
+
+fn all(args: &AllArgs) -> anyhow::Result<()> {
+    memory::set_limit(args.memory_limit)?;
+
+    let mut outcomes = vec![];
+
+    for registration in registry::days()? {
+        let day = registration.id();
+
+        match InputSource::Day(day.clone()).load(registration.solution) {
+            Ok(input) => {
+                outcomes.extend(runner::run_parts(
+                    &day,
+                    registration.solution,
+                    &Part::ALL,
+                    &input,
+                    &Params::defaults(registration.solution.params()),
+                    args.timeout,
+                ));
+            }
+            Err(err) => outcomes.extend(runner::Outcome::failed(
+                &day,
+                &Part::ALL,
+                Duration::ZERO,
+                &Status::Error(err),
+            )),
+        }
+    }
+
+    report::print_outcomes(&outcomes, args.format)?;
+    check_outcomes(&outcomes)
+}
+
+/// Fails if any part failed, after the outcomes have been printed. Parts that
+/// aren't implemented yet don't count, so that a calendar with unfinished days
+/// still passes.
+fn check_outcomes(outcomes: &[runner::Outcome]) -> anyhow::Result<()> {
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| !matches!(outcome.status, Status::Ok(_) | Status::NotImplemented))
+        .count();
 
-        println!("The answer is:\n{answer}");
-    } else {
-        bail!("Day {day} was not found");
+    if failed > 0 {
+        bail!("{failed} of {} part(s) failed", outcomes.len());
     }
 
     Ok(())
 }
 
-fn get_day(day: &str) -> Option<Box<dyn Solution>> {
-    let solution: Box<dyn Solution> = match day {
-        "01" => Box::new(day_01::Day {}),
-        "02" => Box::new(day_02::Day {}),
-        "03" => Box::new(day_03::Day {}),
-        "04" => Box::new(day_04::Day {}),
-        "05" => Box::new(day_05::Day {}),
-        "06" => Box::new(day_06::Day {}),
-        "07" => Box::new(day_07::Day {}),
-        "08" => Box::new(day_08::Day {}),
-        "09" => Box::new(day_09::Day {}),
-        "11" => Box::new(day_11::Day {}),
-        _ => return None,
+fn bench(args: &BenchArgs) -> anyhow::Result<()> {
+    let BenchArgs {
+        day,
+        part,
+        runs,
+        warmup,
+        input,
+        text,
+        example,
+        param,
+    } = args;
+
+    let registration = find_day(day)?;
+    let day = registration.id();
+    let input =
+        InputSource::from_args(&day, input.as_deref(), text.as_deref(), example.as_deref())?
+            .load(registration.solution)?;
+    let part = Part::parse(part)?;
+    let params = Params::new(
+        registration.solution.params(),
+        &param.iter().cloned().collect(),
+    )?;
+
+    let stats = bench::bench(
+        &day,
+        registration.solution,
+        part,
+        &input,
+        &params,
+        *warmup,
+        *runs,
+    )?;
+
+    println!("Day {day} part {part}:\n{stats}");
+
+    Ok(())
+}
+
+fn list() -> anyhow::Result<()> {
+    for registration in registry::days()? {
+        println!("{}  {}", registration.id(), registration.title);
+
+        for param in registration.solution.params() {
+            println!("    {}={}  {}", param.name, param.default, param.help);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a duration like `30s`, `500ms` or `2m`. A bare number is seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit) = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .map_or((value, "s"), |i| value.split_at(i));
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("'{value}' is not a duration"))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "s" => number,
+        "m" => number * 60.0,
+        _ => {
+            return Err(format!(
+                "unknown unit '{unit}' in '{value}', use ms, s or m"
+            ))
+        }
     };
 
-    Some(solution)
+    Duration::try_from_secs_f64(seconds).map_err(|err| format!("'{value}' {err}"))
+}
+
+/// Parses a size in bytes like `512M` or `2G`, with binary units.
+fn parse_size(value: &str) -> Result<usize, String> {
+    let (number, unit) = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .map_or((value, ""), |i| value.split_at(i));
+
+    let number: usize = number
+        .parse()
+        .map_err(|_| format!("'{value}' is not a size"))?;
+    let shift = match unit {
+        "" | "B" => 0,
+        "K" => 10,
+        "M" => 20,
+        "G" => 30,
+        _ => return Err(format!("unknown unit '{unit}' in '{value}', use K, M or G")),
+    };
+
+    number
+        .checked_mul(1 << shift)
+        .ok_or_else(|| format!("'{value}' is too large"))
+}
+
+fn find_day(day: &str) -> anyhow::Result<&'static Registration> {
+    match registry::get(day) {
+        Some(registration) => Ok(registration),
+        None => bail!("Day {day} was not found"),
+    }
 }
@@ -0,0 +1,186 @@
+//! Heap usage of solutions, counted by a global allocator that is only
+//! installed with the `memory` feature. Without it nothing is measured and no
+//! limit can be set.
+//!
+//! The counters are shared by every thread, so that work spread out over
+//! threads is included. That also means a solution that keeps running after
+//! it was stopped would be counted against the calls measured after it, so
+//! measuring stops for good when that happens, see [`stop_measuring`].
+
+#[cfg(feature = "memory")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use anyhow::Result;
+
+/// Heap usage of a single solution call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// The most memory held at once, on top of what was held before the call.
+    pub peak: usize,
+    /// The number of allocations, including reallocations.
+    pub allocations: usize,
+    /// Whether the peak went over the limit.
+    pub exceeded: bool,
+}
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[cfg(feature = "memory")]
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "memory")]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "memory")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Memory held when the measured call started, or `usize::MAX` when nothing
+/// is being measured so that the limit never applies.
+#[cfg(feature = "memory")]
+static BASELINE: AtomicUsize = AtomicUsize::new(usize::MAX);
+#[cfg(feature = "memory")]
+static LIMIT: AtomicUsize = AtomicUsize::new(usize::MAX);
+#[cfg(feature = "memory")]
+static EXCEEDED: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "memory")]
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// Wraps the system allocator, counting every allocation. Going over the
+/// limit only raises a flag: an allocator must not unwind, and failing the
+/// allocation would abort the process, so the runner stops the solution
+/// instead.
+#[cfg(feature = "memory")]
+struct Counting;
+
+#[cfg(feature = "memory")]
+impl Counting {
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+
+        if current.saturating_sub(BASELINE.load(Ordering::Relaxed)) > LIMIT.load(Ordering::Relaxed)
+        {
+            EXCEEDED.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "memory")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Caps the heap growth of each measured call at `limit` bytes.
+#[cfg(feature = "memory")]
+// Fails without the feature, so the signature stays the same for callers.
+#[allow(clippy::unnecessary_wraps)]
+pub fn set_limit(limit: Option<usize>) -> Result<()> {
+    LIMIT.store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(not(feature = "memory"))]
+pub fn set_limit(limit: Option<usize>) -> Result<()> {
+    anyhow::ensure!(
+        limit.is_none(),
+        "a memory limit needs the `memory` feature, build with `--features memory`"
+    );
+    Ok(())
+}
+
+pub fn limit() -> Option<usize> {
+    #[cfg(feature = "memory")]
+    return Some(LIMIT.load(Ordering::Relaxed)).filter(|&limit| limit != usize::MAX);
+
+    #[cfg(not(feature = "memory"))]
+    None
+}
+
+/// Whether the call being measured went over the limit.
+pub fn exceeded() -> bool {
+    #[cfg(feature = "memory")]
+    return !STOPPED.load(Ordering::Relaxed) && EXCEEDED.load(Ordering::Relaxed);
+
+    #[cfg(not(feature = "memory"))]
+    false
+}
+
+/// Stops measuring and enforcing the limit for the rest of the process,
+/// because a call that was stopped is still running. Returns whether anything
+/// was being measured until now.
+pub fn stop_measuring() -> bool {
+    #[cfg(feature = "memory")]
+    {
+        BASELINE.store(usize::MAX, Ordering::Relaxed);
+        !STOPPED.swap(true, Ordering::Relaxed)
+    }
+
+    #[cfg(not(feature = "memory"))]
+    false
+}
+
+/// Calls `f` and measures its heap usage, if the `memory` feature is enabled
+/// and measuring hasn't been stopped.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    #[cfg(feature = "memory")]
+    {
+        if STOPPED.load(Ordering::Relaxed) {
+            return (f(), None);
+        }
+
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        BASELINE.store(baseline, Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        EXCEEDED.store(false, Ordering::Relaxed);
+
+        let value = f();
+
+        BASELINE.store(usize::MAX, Ordering::Relaxed);
+        let usage = Usage {
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            exceeded: EXCEEDED.swap(false, Ordering::Relaxed),
+        };
+
+        (value, Some(usage))
+    }
+
+    #[cfg(not(feature = "memory"))]
+    (f(), None)
+}
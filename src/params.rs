@@ -0,0 +1,109 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+
+/// A value a solution depends on that isn't part of the input, like the
+/// number of steps to take. Examples in the puzzle text often use a different
+/// value than the real input, which is what `default` is for.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+    /// Checks that a value parses as the type the parts read it as.
+    validate: fn(&str) -> Result<(), String>,
+}
+
+impl Param {
+    /// A parameter the parts read as a `T`.
+    pub const fn new<T>(name: &'static str, default: &'static str, help: &'static str) -> Self
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Self {
+            name,
+            default,
+            help,
+            validate: |value| value.parse::<T>().map(drop).map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Fails if `value` doesn't parse as the parameter's type.
+    pub fn check(&self, value: &str) -> Result<()> {
+        match (self.validate)(value) {
+            Ok(()) => Ok(()),
+            Err(err) => bail!(
+                "invalid value '{value}' for parameter '{}': {err}",
+                self.name
+            ),
+        }
+    }
+}
+
+/// The parameter values for a run: the defaults a day declares, with some of
+/// them overridden from the command line or by an example.
+#[derive(Debug, Clone, Default)]
+pub struct Params(BTreeMap<&'static str, String>);
+
+impl Params {
+    /// Applies `overrides` to the defaults of `declared`. Fails on a name that
+    /// isn't declared, or a value that isn't of the parameter's type.
+    pub fn new(declared: &[Param], overrides: &BTreeMap<String, String>) -> Result<Self> {
+        let Self(mut values) = Self::defaults(declared);
+
+        for (name, value) in overrides {
+            match declared.iter().find(|param| param.name == name) {
+                Some(param) => {
+                    param.check(value)?;
+                    values.insert(param.name, value.clone())
+                }
+                None if declared.is_empty() => {
+                    bail!("there are no parameters, so '{name}' can't be set")
+                }
+                None => bail!(
+                    "unknown parameter '{name}', expected one of {}",
+                    declared.iter().map(|param| param.name).join(", ")
+                ),
+            };
+        }
+
+        Ok(Self(values))
+    }
+
+    /// The defaults of `declared`, as used for the real input.
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self(
+            declared
+                .iter()
+                .map(|param| (param.name, param.default.to_string()))
+                .collect(),
+        )
+    }
+
+    /// The value of `name` parsed as a `T`.
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .0
+            .get(name)
+            .context(format!("parameter '{name}' is not declared"))?;
+
+        match value.parse() {
+            Ok(value) => Ok(value),
+            Err(err) => bail!("invalid value '{value}' for parameter '{name}': {err}"),
+        }
+    }
+}
+
+/// Parses a `name=value` pair given to `--param`.
+pub fn parse_assignment(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("'{value}' is not of the form name=value")),
+    }
+}
@@ -0,0 +1,255 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    character::complete::{char, digit1, newline, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::{ErrorKind, FromExternalError},
+    sequence::{pair, preceded, terminated, tuple},
+    Finish, Offset,
+};
+
+/// The result of the parsers in this crate, which fail with a [`NomError`].
+pub type IResult<I, O> = nom::IResult<I, O, NomError<I>>;
+
+/// The error of a nom parser: where it failed and what it expected there.
+#[derive(Debug)]
+pub struct NomError<I> {
+    input: I,
+    expected: Expected,
+}
+
+#[derive(Debug)]
+enum Expected {
+    Kind(ErrorKind),
+    Char(char),
+    /// The text of a [`tag`].
+    Text(&'static str),
+}
+
+impl<I> nom::error::ParseError<I> for NomError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Expected::Kind(kind),
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self {
+            input,
+            expected: Expected::Char(c),
+        }
+    }
+}
+
+impl<I, E> FromExternalError<I, E> for NomError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+/// A parse failure, located by line and column in the input that was parsed.
+#[derive(Debug)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    text: String,
+    expected: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` into `input`, where `expected` describes what
+    /// should have been there.
+    pub fn at(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: input[start..offset].chars().count() + 1,
+            text: input[start..end].to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Locates an error returned by a nom parser that was run on `input`.
+    pub fn from_nom(input: &str, err: &NomError<&str>) -> Self {
+        let expected = match err.expected {
+            Expected::Kind(kind) => describe(kind),
+            Expected::Char('\n') => "a line break".to_string(),
+            Expected::Char(c) => format!("'{}'", c.escape_debug()),
+            Expected::Text(text) => format!("'{}'", text.escape_debug()),
+        };
+
+        Self::at(input, input.offset(err.input), expected)
+    }
+
+    /// Moves the error down by `lines`, for errors in one line of a larger
+    /// input.
+    #[must_use]
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            line,
+            column,
+            text,
+            expected,
+        } = self;
+        let width = line.to_string().len();
+
+        writeln!(f, "expected {expected} at line {line}, column {column}")?;
+        writeln!(f, "{:width$} |", "")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(f, "{:width$} | {:>column$}", "", "^")
+    }
+}
+
+impl Error for ParseError {}
+
+/// Runs `parser` on the whole of `input`, failing if anything but a final
+/// newline is left over.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, opt(newline)))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::from_nom(input, &err))
+}
+
+/// Parses every line of `input` on its own, reporting errors at their line in
+/// `input`.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|err: ParseError| err.below(i)))
+        .collect()
+}
+
+/// Exactly the text `expected`, which errors quote when it is missing.
+pub fn tag<'a>(expected: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |s| {
+        nom::bytes::complete::tag(expected)(s).map_err(|err| {
+            err.map(|err: NomError<&str>| NomError {
+                expected: Expected::Text(expected),
+                ..err
+            })
+        })
+    }
+}
+
+/// A number without a sign, like `42`.
+pub fn unsigned<T: FromStr>(s: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(s)
+}
+
+/// A number with an optional sign, like `-42` or `+42`.
+pub fn signed<T: FromStr>(s: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(s)
+}
+
+/// One or more items on a line, separated by spaces.
+pub fn list<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated(space1, item)
+}
+
+/// One or more unsigned numbers on a line, separated by spaces.
+pub fn numbers<T: FromStr>(s: &str) -> IResult<&str, Vec<T>> {
+    list(unsigned)(s)
+}
+
+/// One or more items, one per line.
+pub fn lines<'a, O>(
+    line: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated(newline, line)
+}
+
+/// One or more blocks of lines, separated by blank lines.
+pub fn blocks<'a, O>(
+    block: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated(tag("\n\n"), block)
+}
+
+/// A `label: value` line, returning the value.
+pub fn labelled<'a, O>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    preceded(tuple((tag(label), char(':'), space0)), value)
+}
+
+/// Like `separated_list1`, except that an item which fails after matching
+/// part of its input fails the whole list. Otherwise the list would end before
+/// that item, and the error would point at where the list stopped rather than
+/// at the mistake.
+fn separated<'a, O, S>(
+    mut separator: impl FnMut(&'a str) -> IResult<&'a str, S>,
+    mut item: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    move |s| {
+        let (mut s, first) = item(s)?;
+        let mut items = vec![first];
+
+        loop {
+            let rest = match separator(s) {
+                Ok((rest, _)) => rest,
+                Err(nom::Err::Error(_)) => return Ok((s, items)),
+                Err(err) => return Err(err),
+            };
+
+            match item(rest) {
+                Ok((rest, value)) => {
+                    items.push(value);
+                    s = rest;
+                }
+                // Nothing of the item matched, so the list ends here.
+                Err(nom::Err::Error(err)) if err.input.len() == rest.len() => {
+                    return Ok((s, items))
+                }
+                Err(nom::Err::Error(err)) => return Err(nom::Err::Failure(err)),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    let expected = match kind {
+        ErrorKind::Tag => "a keyword or separator",
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "a word",
+        ErrorKind::AlphaNumeric => "a word or number",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::Char => "a specific character",
+        ErrorKind::OneOf | ErrorKind::IsA => "one of the allowed characters",
+        ErrorKind::CrLf => "a line break",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+        ErrorKind::TakeUntil => "a terminator",
+        kind => return kind.description().to_lowercase(),
+    };
+
+    expected.to_string()
+}
@@ -0,0 +1,62 @@
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::solution::DynSolution;
+
+/// A day's solution, submitted once from its own module with
+/// `inventory::submit!`.
+pub struct Registration {
+    pub day: u8,
+    pub title: &'static str,
+    pub solution: &'static (dyn DynSolution + Sync),
+}
+
+inventory::collect!(Registration);
+
+impl Registration {
+    pub const fn new(
+        day: u8,
+        title: &'static str,
+        solution: &'static (dyn DynSolution + Sync),
+    ) -> Self {
+        Self {
+            day,
+            title,
+            solution,
+        }
+    }
+
+    /// The zero-padded day number used for input files and on the command line.
+    pub fn id(&self) -> String {
+        format!("{:02}", self.day)
+    }
+}
+
+/// Every registered day sorted by day number. Fails if two modules registered
+/// the same day.
+pub fn days() -> Result<Vec<&'static Registration>> {
+    let days = inventory::iter::<Registration>
+        .into_iter()
+        .sorted_by_key(|registration| registration.day)
+        .collect_vec();
+
+    if let Some((a, b)) = days.iter().tuple_windows().find(|(a, b)| a.day == b.day) {
+        bail!(
+            "Day {} is registered twice, as '{}' and '{}'",
+            a.id(),
+            a.title,
+            b.title
+        );
+    }
+
+    Ok(days)
+}
+
+/// Looks up a day by its number, accepting both `1` and `01`.
+pub fn get(day: &str) -> Option<&'static Registration> {
+    let day = day.parse::<u8>().ok()?;
+
+    inventory::iter::<Registration>
+        .into_iter()
+        .find(|registration| registration.day == day)
+}
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    answer::Answer,
+    runner::{Outcome, Status},
+};
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("unknown format '{s}', expected text, json or csv"),
+        }
+    }
+}
+
+/// A machine-readable version of an [`Outcome`].
+#[derive(Serialize)]
+pub struct Record {
+    pub day: String,
+    pub part: String,
+    pub status: &'static str,
+    pub answer: Option<Answer>,
+    pub parse_secs: f64,
+    pub solve_secs: f64,
+    /// Heap usage of the solve, only measured with the `memory` feature.
+    pub peak_bytes: Option<usize>,
+    pub allocations: Option<usize>,
+    /// The error followed by each of its causes, outermost first.
+    pub error: Vec<String>,
+}
+
+impl From<&Outcome> for Record {
+    fn from(outcome: &Outcome) -> Self {
+        let (answer, error) = match &outcome.status {
+            Status::Ok(answer) => (Some(answer.clone()), vec![]),
+            Status::Error(err) => (None, err.chain().map(ToString::to_string).collect()),
+            Status::NotImplemented => (None, vec![]),
+            Status::Panicked(panic) => (None, vec![panic.to_string()]),
+            Status::TimedOut(timeout) => (None, vec![format!("timed out after {timeout:?}")]),
+            Status::MemoryExceeded(limit) => (
+                None,
+                vec![format!("used more than {} of memory", format_bytes(*limit))],
+            ),
+        };
+
+        Self {
+            day: outcome.day.clone(),
+            part: outcome.part.to_string(),
+            status: outcome.status.label(),
+            answer,
+            parse_secs: outcome.parse.as_secs_f64(),
+            solve_secs: outcome.solve.as_secs_f64(),
+            peak_bytes: outcome.memory.map(|memory| memory.peak),
+            allocations: outcome.memory.map(|memory| memory.allocations),
+            error,
+        }
+    }
+}
+
+/// Prints the outcomes as a table, a JSON array or CSV.
+pub fn print_outcomes(outcomes: &[Outcome], format: Format) -> anyhow::Result<()> {
+    let records = outcomes.iter().map(Record::from).collect_vec();
+
+    match format {
+        Format::Text => {
+            // Memory columns are only shown when something was measured.
+            let memory = outcomes.iter().any(|outcome| outcome.memory.is_some());
+            let mut headers = vec!["Day", "Part", "Answer", "Parse", "Solve"];
+            if memory {
+                headers.extend(["Peak", "Allocs"]);
+            }
+            headers.push("Status");
+
+            let rows = outcomes
+                .iter()
+                .zip(&records)
+                .map(|(outcome, record)| {
+                    let mut row = vec![
+                        record.day.clone(),
+                        record.part.clone(),
+                        record
+                            .answer
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                        format!("{:.2?}", outcome.parse),
+                        format!("{:.2?}", outcome.solve),
+                    ];
+                    if memory {
+                        row.push(record.peak_bytes.map(format_bytes).unwrap_or_default());
+                        row.push(
+                            record
+                                .allocations
+                                .map(|n| n.to_string())
+                                .unwrap_or_default(),
+                        );
+                    }
+                    row.push(record.status.to_string());
+                    row
+                })
+                .collect_vec();
+
+            print_table(&headers, &rows);
+
+            // Errors can span several lines, so they go below the table.
+            for record in records.iter().filter(|record| !record.error.is_empty()) {
+                println!(
+                    "\nDay {} part {}: {}",
+                    record.day,
+                    record.part,
+                    record.error.join(": ")
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records)?),
+        Format::Csv => {
+            println!("day,part,status,answer,parse_secs,solve_secs,peak_bytes,allocations,error");
+            for record in &records {
+                let fields = [
+                    record.day.clone(),
+                    record.part.clone(),
+                    record.status.to_string(),
+                    record
+                        .answer
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    record.parse_secs.to_string(),
+                    record.solve_secs.to_string(),
+                    record.peak_bytes.map(|n| n.to_string()).unwrap_or_default(),
+                    record
+                        .allocations
+                        .map(|n| n.to_string())
+                        .unwrap_or_default(),
+                    record.error.join(": "),
+                ];
+                println!("{}", fields.iter().map(|field| csv_field(field)).join(","));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a number of bytes with a binary unit, like `1.50 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{size:.2} {}", UNITS[unit])
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Prints `rows` as left-aligned columns under `headers`.
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let widths = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect_vec();
+
+    let format_row = |row: &[&str]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(headers));
+    println!("{}", widths.iter().map(|w| "-".repeat(*w)).join("  "));
+    for row in rows {
+        println!(
+            "{}",
+            format_row(&row.iter().map(String::as_str).collect_vec())
+        );
+    }
+}
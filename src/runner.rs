@@ -0,0 +1,389 @@
+use std::{
+    any::Any,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail};
+
+use crate::{
+    answer::Answer,
+    cancel::CancelToken,
+    memory::{self, Usage},
+    params::Params,
+    solution::DynSolution,
+};
+
+const TODO_MESSAGE: &str = "not yet implemented";
+
+/// How many solution calls are currently isolated by the runner. Panics while
+/// this is non-zero are reported in the results, so the hook stays quiet.
+static ISOLATED: AtomicUsize = AtomicUsize::new(0);
+
+/// How long a stopped solution gets to notice its cancellation and return.
+const STOP_GRACE: Duration = Duration::from_secs(1);
+
+/// How often the runner checks on a running solution.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Where the last panic happened. The payload only carries the message, so the
+/// hook stores the location for the runner to pick up.
+static LAST_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn parse(part: &str) -> anyhow::Result<Self> {
+        match part {
+            "01" | "1" => Ok(Part::One),
+            "02" | "2" => Ok(Part::Two),
+            _ => bail!("Part {part} was not found"),
+        }
+    }
+
+    /// Parses the part argument of the CLI, where `both` or no part at all
+    /// selects both parts.
+    pub fn parse_selection(part: Option<&str>) -> anyhow::Result<Vec<Self>> {
+        match part {
+            None | Some("both") => Ok(Part::ALL.to_vec()),
+            Some(part) => Ok(vec![Part::parse(part)?]),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "01"),
+            Part::Two => write!(f, "02"),
+        }
+    }
+}
+
+pub enum Status {
+    Ok(Answer),
+    Error(anyhow::Error),
+    NotImplemented,
+    Panicked(Panic),
+    TimedOut(Duration),
+    /// Stopped for holding more than this many bytes.
+    MemoryExceeded(usize),
+}
+
+/// A panic caught while running a solution.
+#[derive(Debug, Clone)]
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+impl Status {
+    /// A copy of a failed status for another part. Errors aren't `Clone`, so
+    /// the copy is rebuilt from the messages of the error chain.
+    pub fn duplicate(&self) -> Self {
+        match self {
+            Status::Ok(answer) => Status::Ok(answer.clone()),
+            Status::Error(err) => {
+                let mut messages = err.chain().map(ToString::to_string).rev();
+                let root = anyhow!(messages.next().unwrap_or_default());
+                Status::Error(messages.fold(root, anyhow::Error::context))
+            }
+            Status::NotImplemented => Status::NotImplemented,
+            Status::Panicked(panic) => Status::Panicked(panic.clone()),
+            Status::TimedOut(timeout) => Status::TimedOut(*timeout),
+            Status::MemoryExceeded(limit) => Status::MemoryExceeded(*limit),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok(_) => "ok",
+            Status::Error(_) => "error",
+            Status::NotImplemented => "not implemented",
+            Status::Panicked(_) => "panicked",
+            Status::TimedOut(_) => "timed out",
+            Status::MemoryExceeded(_) => "memory exceeded",
+        }
+    }
+}
+
+pub struct Outcome {
+    pub day: String,
+    pub part: Part,
+    /// Time spent parsing the input. When several parts are run together they
+    /// share a single parse, and each reports its duration.
+    pub parse: Duration,
+    pub solve: Duration,
+    /// Heap usage of the solve, when built with the `memory` feature.
+    pub memory: Option<Usage>,
+    pub status: Status,
+}
+
+impl Outcome {
+    /// Outcomes for parts that never got to run, because the input couldn't
+    /// be read or parsed.
+    pub fn failed(day: &str, parts: &[Part], parse: Duration, status: &Status) -> Vec<Self> {
+        parts
+            .iter()
+            .map(|&part| Self {
+                day: day.to_string(),
+                part,
+                parse,
+                solve: Duration::ZERO,
+                memory: None,
+                status: status.duplicate(),
+            })
+            .collect()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Runs a single part of a solution, see [`run_parts`].
+pub fn run(
+    day: &str,
+    solution: &'static (dyn DynSolution + Sync),
+    part: Part,
+    input: &str,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Outcome {
+    let mut outcomes = run_parts(day, solution, &[part], input, params, timeout);
+    outcomes.remove(0)
+}
+
+enum Event {
+    Parsed(Duration),
+    Solved(Outcome),
+}
+
+/// Parses the input once and runs each of `parts` on the result, timing the
+/// parse and each solve separately. `todo!()` is reported as
+/// [`Status::NotImplemented`] and any other panic as [`Status::Panicked`].
+///
+/// The solution runs on its own thread. When a call takes longer than
+/// `timeout`, or goes over the memory limit, its [`CancelToken`] is cancelled
+/// and the parts that are left are reported as [`Status::TimedOut`] or
+/// [`Status::MemoryExceeded`] without waiting for the thread to finish.
+pub fn run_parts(
+    day: &str,
+    solution: &'static (dyn DynSolution + Sync),
+    parts: &[Part],
+    input: &str,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Vec<Outcome> {
+    let cancel = CancelToken::new();
+    let (sender, receiver) = mpsc::channel();
+
+    {
+        let (day, parts, input, params, cancel) = (
+            day.to_string(),
+            parts.to_vec(),
+            input.to_string(),
+            params.clone(),
+            cancel.clone(),
+        );
+
+        thread::spawn(move || {
+            cancel.make_current();
+            solve(&day, solution, &parts, &input, &params, |event| {
+                // The receiver is gone once the run has timed out.
+                let _ = sender.send(event);
+            });
+        });
+    }
+
+    let mut parse = None;
+    let mut outcomes = vec![];
+    let mut started = Instant::now();
+
+    let status = loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(Event::Parsed(duration)) => {
+                parse = Some(duration);
+                started = Instant::now();
+            }
+            Ok(Event::Solved(outcome)) => {
+                outcomes.push(outcome);
+                if outcomes.len() == parts.len() {
+                    return outcomes;
+                }
+                started = Instant::now();
+            }
+            Err(RecvTimeoutError::Timeout) if memory::exceeded() => {
+                break Status::MemoryExceeded(memory::limit().unwrap_or_default());
+            }
+            Err(RecvTimeoutError::Timeout) => match timeout {
+                Some(timeout) if started.elapsed() >= timeout => break Status::TimedOut(timeout),
+                _ => (),
+            },
+            Err(RecvTimeoutError::Disconnected) => {
+                break Status::Error(anyhow!("the solution stopped without an answer"));
+            }
+        }
+    };
+
+    let elapsed = started.elapsed();
+    cancel.cancel();
+
+    // A solution that doesn't stop keeps running on its own thread, and its
+    // allocations would be counted against everything measured after it.
+    let deadline = Instant::now() + STOP_GRACE;
+    let stopped = loop {
+        match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(_) => (),
+            Err(RecvTimeoutError::Disconnected) => break true,
+            Err(RecvTimeoutError::Timeout) => break false,
+        }
+    };
+    if !stopped && memory::stop_measuring() {
+        eprintln!("warning: day {day} is still running after being stopped, so memory is no longer measured");
+    }
+
+    let mut rest = Outcome::failed(
+        day,
+        &parts[outcomes.len()..],
+        parse.unwrap_or(elapsed),
+        &status,
+    );
+
+    // Once the input is parsed, the first part left is the one that was
+    // stopped.
+    if let (Some(_), Some(first)) = (parse, rest.first_mut()) {
+        first.solve = elapsed;
+    }
+
+    outcomes.extend(rest);
+    outcomes
+}
+
+fn solve(
+    day: &str,
+    solution: &dyn DynSolution,
+    parts: &[Part],
+    input: &str,
+    params: &Params,
+    send: impl Fn(Event),
+) {
+    let start = Instant::now();
+    let parsed = isolate(|| solution.parse(input));
+    let parse = start.elapsed();
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(status) => {
+            for outcome in Outcome::failed(day, parts, parse, &status) {
+                send(Event::Solved(outcome));
+            }
+            return;
+        }
+    };
+
+    send(Event::Parsed(parse));
+
+    for &part in parts {
+        if CancelToken::current().is_cancelled() {
+            return;
+        }
+
+        let start = Instant::now();
+        let (result, memory) = memory::measure(|| {
+            isolate(|| match part {
+                Part::One => solution.part1(parsed.as_ref(), params),
+                Part::Two => solution.part2(parsed.as_ref(), params),
+            })
+        });
+        let solve = start.elapsed();
+
+        let status = match result {
+            _ if memory.is_some_and(|memory| memory.exceeded) => {
+                Status::MemoryExceeded(memory::limit().unwrap_or_default())
+            }
+            Ok(answer) => Status::Ok(answer),
+            Err(status) => status,
+        };
+
+        send(Event::Solved(Outcome {
+            day: day.to_string(),
+            part,
+            parse,
+            solve,
+            memory,
+            status,
+        }));
+    }
+}
+
+/// Calls `f`, turning errors and panics into a [`Status`], so that one failing
+/// solution doesn't take down the rest of the run.
+fn isolate<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Result<T, Status> {
+    ISOLATED.fetch_add(1, Ordering::SeqCst);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED.fetch_sub(1, Ordering::SeqCst);
+
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(err)) => Err(Status::Error(err)),
+        Err(payload) if is_todo(payload.as_ref()) => Err(Status::NotImplemented),
+        Err(payload) => Err(Status::Panicked(Panic {
+            message: message(payload.as_ref())
+                .unwrap_or("Box<dyn Any>")
+                .to_string(),
+            location: LAST_LOCATION
+                .lock()
+                .map_or(None, |mut location| location.take()),
+        })),
+    }
+}
+
+/// Records where panics happen and silences the message printed for panics
+/// the runner catches, since it reports those itself. Panics elsewhere are
+/// printed as usual.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Ok(mut location) = LAST_LOCATION.lock() {
+            *location = info.location().map(ToString::to_string);
+        }
+
+        if ISOLATED.load(Ordering::SeqCst) == 0 {
+            default_hook(info);
+        }
+    }));
+}
+
+fn is_todo(payload: &(dyn Any + Send)) -> bool {
+    message(payload).is_some_and(|m| m.starts_with(TODO_MESSAGE))
+}
+
+fn message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
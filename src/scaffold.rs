@@ -0,0 +1,92 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, ensure, Context, Result};
+
+use crate::registry;
+
+/// Creates `src/day_NN.rs` from the `day_XX.rs` template and wires it into
+/// `main.rs`, `answers.toml` and the input directory.
+pub fn new_day(day: &str, title: &str) -> Result<()> {
+    let number = day
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .context(format!("Day {day} is not between 1 and 25"))?;
+    let day = format!("{number:02}");
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let module = root.join(format!("src/day_{day}.rs"));
+
+    ensure!(
+        registry::get(&day).is_none() && !module.exists(),
+        "Day {day} already exists"
+    );
+
+    let template = read(&root.join("src/day_XX.rs"))?;
+    let solution = template
+        .replace("XX", &number.to_string())
+        .replace("TITLE", title);
+    fs::write(&module, solution).context(format!("Failed to write {}", module.display()))?;
+
+    add_module(&root.join("src/main.rs"), &day)?;
+    add_answers(&root.join("answers.toml"), &day)?;
+
+    create_empty(&root.join("input").join(&day))?;
+    create_empty(&root.join("input/examples").join(&day).join("example"))?;
+
+    println!("Created day {day} in {}", module.display());
+
+    Ok(())
+}
+
+/// Inserts `mod day_NN;` among the other day modules, keeping them sorted.
+fn add_module(main: &Path, day: &str) -> Result<()> {
+    let contents = read(main)?;
+    let declaration = format!("mod day_{day};");
+
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let Some(last) = lines.iter().rposition(|line| line.starts_with("mod day_")) else {
+        bail!("No day modules found in {}", main.display());
+    };
+    let position = lines
+        .iter()
+        .position(|line| line.starts_with("mod day_") && *line > declaration.as_str())
+        .unwrap_or(last + 1);
+    lines.insert(position, &declaration);
+
+    fs::write(main, lines.join("\n") + "\n").context(format!("Failed to write {}", main.display()))
+}
+
+/// Adds empty tables for the day, to be filled in by `verify --record`, and for
+/// its example.
+fn add_answers(answers: &Path, day: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(answers)
+        .context(format!("Failed to open {}", answers.display()))?;
+
+    write!(file, "\n[\"{day}\"]\n\n[\"{day}\".examples.example]\n")
+        .context(format!("Failed to write {}", answers.display()))
+}
+
+/// Creates an empty file unless it already exists, so inputs that were
+/// downloaded before the day was scaffolded are kept.
+fn create_empty(path: &Path) -> Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(format!("Failed to create {}", parent.display()))?;
+    }
+
+    fs::write(path, "").context(format!("Failed to write {}", path.display()))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).context(format!("Failed to read {}", path.display()))
+}
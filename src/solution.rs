@@ -1,4 +1,69 @@
+use std::any::Any;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    answer::Answer,
+    params::{Param, Params},
+};
+
 pub trait Solution {
-    fn compute_1(&self, input: &str) -> anyhow::Result<String>;
-    fn compute_2(&self, input: &str) -> anyhow::Result<String>;
+    /// The parsed input, shared by both parts.
+    type Parsed;
+
+    /// Whether whitespace at the end of lines and of the input can be removed
+    /// before parsing. Days where it is significant turn this off.
+    const TRIM_WHITESPACE: bool = true;
+
+    /// The parameters the parts are given values for.
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed>;
+    fn part1(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
+    fn part2(&self, parsed: &Self::Parsed, params: &Params) -> Result<Answer>;
+}
+
+/// [`Solution`] with the parsed type erased, so that days with different
+/// `Parsed` types can be stored and run side by side.
+pub trait DynSolution {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn part1(&self, parsed: &dyn Any, params: &Params) -> Result<Answer>;
+    fn part2(&self, parsed: &dyn Any, params: &Params) -> Result<Answer>;
+    fn trims_whitespace(&self) -> bool;
+    fn params(&self) -> &'static [Param];
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution,
+    S::Parsed: 'static,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn part1(&self, parsed: &dyn Any, params: &Params) -> Result<Answer> {
+        Solution::part1(self, downcast::<S>(parsed)?, params)
+    }
+
+    fn part2(&self, parsed: &dyn Any, params: &Params) -> Result<Answer> {
+        Solution::part2(self, downcast::<S>(parsed)?, params)
+    }
+
+    fn trims_whitespace(&self) -> bool {
+        S::TRIM_WHITESPACE
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> Result<&S::Parsed>
+where
+    S::Parsed: 'static,
+{
+    parsed
+        .downcast_ref()
+        .context("parsed input has an unexpected type")
 }
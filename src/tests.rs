@@ -1,58 +1,683 @@
-#[cfg(test)]
-mod tests {
-    use crate::solution::Solution;
-    use anyhow::Context;
-    use std::fs::read_to_string;
-
-    macro_rules! day_test {
-        ($day:literal, $part1:literal) => {
-            paste::item! {
-                #[test]
-                fn [<day_$day>] () {
-                    let input = read_input($day);
-                    let day = crate::[<day_$day>]::Day {};
-
-                    let answer = $part1;
-                    let result = day.compute_1(&input).unwrap();
-                    assert_eq!(result, answer);
-                }
-            }
-        };
-        ($day:literal, $part1:literal, $part2:literal) => {
-            paste::item! {
-                #[test]
-                fn [<day_$day>] () {
-                    let input = read_input($day);
-                    let day = crate::[<day_$day>]::Day {};
-
-                    let answer = $part1;
-                    let result = day.compute_1(&input).unwrap();
-                    assert_eq!(result, answer);
-
-                    let answer = $part2;
-                    let result = day.compute_2(&input).unwrap();
-                    assert_eq!(result, answer);
+use std::time::Duration;
+
+use crate::{
+    answer::Answer,
+    answers::Answers,
+    cancel::CancelToken,
+    geometry::{self, Direction, Direction8, IPoint, UPoint},
+    grid::Grid,
+    input::{self, InputSource},
+    params::{Param, Params},
+    parsing, registry,
+    runner::{self, Part, Status},
+    solution::DynSolution,
+    verify::Verdict,
+};
+
+include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
+
+/// Keeps a slow day from hanging the test run.
+const TIMEOUT: Duration = Duration::from_mins(1);
+
+fn check_answer(day: &str, part: Part) {
+    let answers = Answers::load().unwrap();
+    let answers = answers.day(day).unwrap();
+    let expected = answers.get(part).unwrap();
+
+    let input = read_input(day);
+    assert!(
+        answers.matches_input(&input),
+        "the answers for day {day} were recorded for a different input"
+    );
+
+    let params = Params::defaults(solution(day).params());
+    check_run(day, part, &input, &params, expected);
+}
+
+fn check_example(day: &str, name: &str, part: Part) {
+    let answers = Answers::load().unwrap();
+    let example = answers
+        .day(day)
+        .and_then(|answers| answers.examples.get(name))
+        .unwrap();
+    let expected = example.get(part).unwrap();
+
+    let input = example.source(day, name).load(solution(day)).unwrap();
+    let params = Params::new(solution(day).params(), &example.param_overrides()).unwrap();
+
+    check_run(day, part, &input, &params, expected);
+}
+
+fn check_run(day: &str, part: Part, input: &str, params: &Params, expected: &Answer) {
+    match runner::run(day, solution(day), part, input, params, Some(TIMEOUT)).status {
+        Status::Ok(answer) => assert_eq!(answer, *expected),
+        Status::Error(err) => panic!("{err:?}"),
+        Status::NotImplemented => panic!("day {day} part {part} is not implemented"),
+        Status::Panicked(panic) => panic!("day {day} part {part} {panic}"),
+        Status::TimedOut(timeout) => panic!("day {day} part {part} timed out after {timeout:?}"),
+        Status::MemoryExceeded(_) => panic!("day {day} part {part} used too much memory"),
+    }
+}
+
+#[test]
+fn registry_has_unique_days() {
+    registry::days().unwrap();
+}
+
+#[test]
+fn param_defaults_are_valid() {
+    for registration in registry::days().unwrap() {
+        for param in registration.solution.params() {
+            param.check(param.default).unwrap();
+        }
+    }
+}
+
+fn solution(day: &str) -> &'static (dyn DynSolution + Sync) {
+    registry::get(day)
+        .unwrap_or_else(|| panic!("day {day} has answers but is not registered"))
+        .solution
+}
+
+fn read_input(day: &str) -> String {
+    InputSource::Day(day.to_string())
+        .load(solution(day))
+        .unwrap()
+}
+
+#[test]
+fn answers_compare_by_value() {
+    assert_eq!(Answer::from(42_usize), Answer::from(42_i64));
+    assert_eq!(Answer::from(-3_i32), "-3");
+    assert_eq!("0042".parse::<Answer>().unwrap(), 42_u8);
+    assert_ne!(Answer::from("0042"), 42_u8);
+    assert_ne!(Answer::from("+5"), 5_u8);
+    assert_eq!(Answer::from("+5"), "+5");
+    assert_eq!(Answer::from("82"), 82_u8);
+    assert_eq!(Answer::from("-82"), -82_i8);
+    assert_eq!(Answer::from(u128::MAX), u128::MAX);
+    assert_ne!(Answer::from(-1_i64), Answer::from(u128::MAX));
+    assert_ne!(Answer::from("7 days"), 7_usize);
+    assert_eq!(serde_json::to_string(&Answer::from(7_usize)).unwrap(), "7");
+}
+
+#[test]
+fn answers_files_keep_text_verbatim() {
+    #[derive(serde::Deserialize)]
+    struct Row {
+        number: Answer,
+        text: Answer,
+    }
+
+    let row: Row = toml::from_str("number = 42\ntext = \"0042\"").unwrap();
+    assert!(matches!(row.number, Answer::Unsigned(42)));
+    assert!(matches!(&row.text, Answer::Text(text) if text == "0042"));
+    assert_ne!(row.text, 42_u8);
+}
+
+#[test]
+fn parse_errors_point_at_the_failure() {
+    use nom::character::complete::{digit1, newline};
+
+    let input = "12\n34x\n";
+    let err = parsing::parse_all(input, |s| {
+        let (s, _) = digit1(s)?;
+        let (s, _) = newline(s)?;
+        digit1(s)
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "expected the end of the input at line 2, column 3\n  |\n2 | 34x\n  |   ^"
+    );
+
+    // Missing characters and keywords are named.
+    let races = |input: &str| {
+        parsing::parse_all(input, |s| {
+            parsing::labelled("Time", parsing::numbers::<u8>)(s)
+        })
+        .unwrap_err()
+        .to_string()
+    };
+    assert_eq!(
+        races("Time 7"),
+        "expected ':' at line 1, column 5\n  |\n1 | Time 7\n  |     ^"
+    );
+    assert_eq!(
+        races("Tme: 7"),
+        "expected 'Time' at line 1, column 1\n  |\n1 | Tme: 7\n  | ^"
+    );
+}
+
+#[test]
+fn parse_errors_inside_a_list_item_are_not_backtracked() {
+    use crate::day_05::Almanac;
+
+    let input = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-x map:\n5 5";
+    assert_eq!(
+        input.parse::<Almanac>().unwrap_err().to_string(),
+        "expected whitespace at line 7, column 4\n  |\n7 | 5 5\n  |    ^"
+    );
+
+    let input = "x: 1\n\nx: 2\nx: y\n";
+    let parser = parsing::blocks(parsing::lines(parsing::labelled(
+        "x",
+        parsing::unsigned::<u8>,
+    )));
+    assert_eq!(
+        parsing::parse_all(input, parser).unwrap_err().to_string(),
+        "expected a number at line 4, column 4\n  |\n4 | x: y\n  |    ^"
+    );
+}
+
+#[test]
+fn panics_are_isolated_per_part() {
+    struct Panicky;
+
+    impl crate::solution::Solution for Panicky {
+        type Parsed = Vec<u8>;
+
+        fn parse(&self, input: &str) -> anyhow::Result<Self::Parsed> {
+            Ok(input.bytes().collect())
+        }
+
+        fn part1(&self, parsed: &Self::Parsed, _params: &Params) -> anyhow::Result<Answer> {
+            Ok(parsed[10].into())
+        }
+
+        fn part2(&self, _parsed: &Self::Parsed, _params: &Params) -> anyhow::Result<Answer> {
+            todo!()
+        }
+    }
+
+    let outcomes = runner::run_parts(
+        "00",
+        &Panicky,
+        &Part::ALL,
+        "input",
+        &Params::default(),
+        None,
+    );
+
+    assert!(matches!(
+        &outcomes[0].status,
+        Status::Panicked(panic) if panic.message.contains("out of bounds")
+    ));
+    assert!(matches!(outcomes[1].status, Status::NotImplemented));
+}
+
+#[test]
+fn slow_parts_time_out() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+    static PART2_RAN: AtomicBool = AtomicBool::new(false);
+
+    struct Slow;
+
+    impl crate::solution::Solution for Slow {
+        type Parsed = ();
+
+        fn parse(&self, _input: &str) -> anyhow::Result<Self::Parsed> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &Self::Parsed, _params: &Params) -> anyhow::Result<Answer> {
+            let cancel = CancelToken::current();
+            let poll = || -> anyhow::Result<Answer> {
+                loop {
+                    cancel.check()?;
+                    std::thread::sleep(Duration::from_millis(1));
                 }
-            }
-        };
+            };
+            let result = poll();
+
+            // Takes a moment to wind down, which the runner waits for.
+            std::thread::sleep(Duration::from_millis(20));
+            let cancelled = matches!(&result, Err(err) if err.is::<crate::cancel::Cancelled>());
+            STOPPED.store(cancelled, Ordering::SeqCst);
+            result
+        }
+
+        fn part2(&self, _parsed: &Self::Parsed, _params: &Params) -> anyhow::Result<Answer> {
+            PART2_RAN.store(true, Ordering::SeqCst);
+            Ok(2.into())
+        }
+    }
+
+    let timeout = Duration::from_millis(50);
+    let outcomes = runner::run_parts(
+        "00",
+        &Slow,
+        &Part::ALL,
+        "",
+        &Params::default(),
+        Some(timeout),
+    );
+
+    assert!(matches!(outcomes[0].status, Status::TimedOut(t) if t == timeout));
+    assert!(matches!(outcomes[1].status, Status::TimedOut(_)));
+    assert!(STOPPED.load(Ordering::SeqCst));
+    assert!(!PART2_RAN.load(Ordering::SeqCst));
+}
+
+#[test]
+fn grid_parses_and_navigates() {
+    let grid = Grid::parse("ab\ncd\nef\n", "a letter", |c| {
+        c.is_ascii_lowercase().then_some(c)
+    })
+    .unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (2, 3));
+    assert_eq!(grid[UPoint::new(1, 2)], 'f');
+    assert_eq!(grid.get(UPoint::new(2, 0)), None);
+    assert_eq!(
+        grid.neighbours4(UPoint::new(0, 0)).collect::<Vec<_>>(),
+        [UPoint::new(1, 0), UPoint::new(0, 1)]
+    );
+    assert_eq!(grid.neighbours8(UPoint::new(1, 1)).count(), 5);
+    assert_eq!(
+        grid.rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>(),
+        ["ab", "cd", "ef"]
+    );
+    assert_eq!(
+        grid.columns()
+            .map(Iterator::collect::<String>)
+            .collect::<Vec<_>>(),
+        ["ace", "bdf"]
+    );
+    assert_eq!(grid.map(char::to_ascii_uppercase).to_string(), "AB\nCD\nEF");
+
+    assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+    assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+    assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace");
+    assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+
+    let err = Grid::parse("ab\ncD", "a letter", |c| {
+        c.is_ascii_lowercase().then_some(c)
+    })
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("expected a letter at line 2, column 2"));
+    let err = Grid::parse("ab\nc", "a letter", Some).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("expected a row of 2 cells at line 2, column 2"));
+}
+
+#[test]
+fn geometry_basics() {
+    let (a, b) = (IPoint::new(1, -2), IPoint::new(-3, 4));
+    assert_eq!(a + b, IPoint::new(-2, 2));
+    assert_eq!(a - b, IPoint::new(4, -6));
+    assert_eq!(a.manhattan_distance(&b), 10);
+    assert_eq!(a.chebyshev_distance(&b), 6);
+    assert_eq!(UPoint::try_from(a).ok(), None);
+    assert_eq!(
+        UPoint::new(2, 3).checked_add(Direction::Left.vector() * 2),
+        Some(UPoint::new(0, 3))
+    );
+    assert_eq!(
+        UPoint::new(0, 3).checked_add(Direction::Left.vector()),
+        None
+    );
+
+    assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Left.opposite(), Direction::Right);
+    assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+    assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+    assert_eq!(Direction8::from(Direction::Down), Direction8::Down);
+
+    // A 5 by 4 rectangle has 18 points on its edges and 3 rows of 4 inside.
+    let rectangle = [(0, 0), (5, 0), (5, 4), (0, 4)].map(|(x, y)| IPoint::new(x, y));
+    let double_area = geometry::double_area(&rectangle);
+    let boundary = geometry::boundary_points(&rectangle);
+    assert_eq!((double_area, boundary), (40, 18));
+    assert_eq!(geometry::interior_points(double_area, boundary), 12);
+}
+
+#[test]
+fn parsing_helpers_cover_common_layouts() {
+    use parsing::{blocks, labelled, lines, list, numbers, parse_all, signed};
+
+    let values: Vec<u32> = parse_all("seeds: 1 2  3\n", labelled("seeds", numbers)).unwrap();
+    assert_eq!(values, [1, 2, 3]);
+
+    let values: Vec<Vec<i64>> = parse_all("-1 +2\n3", lines(list(signed))).unwrap();
+    assert_eq!(values, [vec![-1, 2], vec![3]]);
+
+    let values: Vec<Vec<Vec<u8>>> = parse_all("1\n2\n\n3", blocks(lines(numbers))).unwrap();
+    assert_eq!(values, [vec![vec![1], vec![2]], vec![vec![3]]]);
+
+    assert!(parse_all("1 2\n\n", numbers::<u8>).is_err());
+}
+
+#[test]
+fn inputs_are_normalised() {
+    assert_eq!(
+        input::normalise("\u{feff}1 2\r\n3 4  \r\n\r\n", true),
+        (
+            "1 2\n3 4\n".to_string(),
+            vec![
+                "removed a byte order mark",
+                "converted CRLF line endings",
+                "removed trailing whitespace"
+            ]
+        )
+    );
+    assert_eq!(
+        input::normalise("#..\n.# \n", false),
+        ("#..\n.# \n".to_string(), vec![])
+    );
+    assert_eq!(input::normalise("1\n2", true), ("1\n2".to_string(), vec![]));
+}
+
+#[test]
+fn params_override_defaults() {
+    const PARAMS: &[Param] = &[
+        Param::new::<usize>("steps", "64", "number of steps"),
+        Param::new::<String>("name", "start", "where to begin"),
+    ];
+
+    let overrides = [("steps".to_string(), "6".to_string())].into();
+    let params = Params::new(PARAMS, &overrides).unwrap();
+    assert_eq!(params.get::<usize>("steps").unwrap(), 6);
+    assert_eq!(params.get::<String>("name").unwrap(), "start");
+    assert!(params.get::<u8>("missing").is_err());
+
+    let invalid = [("steps".to_string(), "x".to_string())].into();
+    assert_eq!(
+        Params::new(PARAMS, &invalid).unwrap_err().to_string(),
+        "invalid value 'x' for parameter 'steps': invalid digit found in string"
+    );
+
+    let unknown = [("size".to_string(), "7".to_string())].into();
+    assert_eq!(
+        Params::new(PARAMS, &unknown).unwrap_err().to_string(),
+        "unknown parameter 'size', expected one of steps, name"
+    );
+}
+
+#[test]
+fn day_05_maps_compose_into_one() {
+    use crate::day_05::Almanac;
+
+    let almanac = |first_map: &str| {
+        let maps = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let entries = if i == 0 { first_map } else { "0 0 1" };
+            format!("{name} map:\n{entries}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+        format!("seeds: 1\n\n{maps}").parse::<Almanac>()
+    };
+
+    let chain = almanac("52 50 48\n50 98 2")
+        .unwrap()
+        .chain("seed", "location")
+        .unwrap();
+    assert_eq!(chain.to_string(), "52 50 48\n50 98 2");
+    assert_eq!(chain.convert(49), 49);
+    assert_eq!(chain.convert(97), 99);
+    assert_eq!(chain.convert(99), 51);
+    assert_eq!(chain.convert(100), 100);
+    assert_eq!(
+        chain.convert_range(40..101),
+        [40..50, 52..100, 50..52, 100..101]
+    );
+
+    assert_eq!(
+        almanac("50 98 2\n52 50 49").unwrap_err().to_string(),
+        "expected a source range that doesn't overlap the one on line 4 at line 5, column 1\n  \
+         |\n5 | 52 50 49\n  | ^"
+    );
+
+    let example = InputSource::Example {
+        day: "05".to_string(),
+        name: "example".to_string(),
+    };
+    let chain = example
+        .read()
+        .unwrap()
+        .parse::<Almanac>()
+        .unwrap()
+        .chain("seed", "location")
+        .unwrap();
+    for (seed, location) in [(79, 82), (14, 43), (55, 86), (13, 35)] {
+        assert_eq!(chain.convert(seed), location);
+    }
+    for seed in 0..120 {
+        let location = chain.convert(seed);
+        let pieces = chain.convert_range(seed..seed + 1);
+        assert_eq!(
+            pieces.iter().map(|piece| piece.start).collect::<Vec<_>>(),
+            [location]
+        );
     }
+}
 
-    fn read_input(day: &str) -> String {
-        let file = format!("./input/{day}");
+#[test]
+fn day_05_chains_are_found_by_name() {
+    use crate::day_05::Almanac;
 
-        read_to_string(&file)
-            .context(format!("Failed to read {file}"))
+    let chain = |maps: &[&str], source: &str, target: &str| {
+        let maps = maps
+            .iter()
+            .map(|name| format!("{name} map:\n0 0 1"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        format!("seeds: 1\n\n{maps}")
+            .parse::<Almanac>()
             .unwrap()
+            .chain(source, target)
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    };
+
+    assert_eq!(chain(&["a-to-b", "c-to-d", "b-to-c"], "a", "d"), Ok(()));
+    assert_eq!(chain(&["a-to-b", "b-to-c"], "b", "b"), Ok(()));
+    assert_eq!(
+        chain(&["a-to-b", "b-to-c", "b-to-x"], "a", "d"),
+        Err("there are no maps from a to d, only to b, c, x".to_string())
+    );
+    assert_eq!(
+        chain(&["a-to-b"], "d", "a"),
+        Err("there are no maps from d".to_string())
+    );
+    assert_eq!(
+        chain(&["a-to-b", "b-to-d", "a-to-c", "c-to-d"], "a", "d"),
+        Err("there is more than one way from a to d: a -> b -> d and a -> c -> d".to_string())
+    );
+    assert_eq!(
+        chain(&["a-to-b", "b-to-c", "c-to-a"], "a", "d"),
+        Err("the maps form a cycle: a -> b -> c -> a".to_string())
+    );
+
+    let duplicate = "seeds: 1\n\na-to-b map:\n0 0 1\n\na-to-b map:\n1 1 1";
+    assert_eq!(
+        duplicate.parse::<Almanac>().unwrap_err().to_string(),
+        "expected a map other than the one on line 3 at line 6, column 1\n  |\n6 | a-to-b map:\n  | ^"
+    );
+}
+
+#[test]
+fn day_05_ranges_past_the_largest_value_are_rejected() {
+    use crate::day_05::Almanac;
+
+    let error = |input: &str| input.parse::<Almanac>().unwrap_err().to_string();
+    let max = usize::MAX;
+
+    assert_eq!(
+        error(&format!("seeds: 1 2\n\nseed-to-location map:\n0 {max} 2")),
+        format!(
+            "expected a length that ends the range by {max} at line 4, column 24\n  |\n4 | 0 {max} 2\n  |                        ^"
+        )
+    );
+    assert_eq!(
+        error(&format!("seeds: 1 2\n\nseed-to-location map:\n{max} 0 1")),
+        format!(
+            "expected a length that ends the range by {max} at line 4, column 24\n  |\n4 | {max} 0 1\n  |                        ^"
+        )
+    );
+    assert_eq!(
+        error(&format!("seeds: 3 1 {max} 2\n\nseed-to-location map:\n0 1 2")),
+        format!(
+            "expected a length that ends the range by {max} at line 1, column 33\n  |\n1 | seeds: 3 1 {max} 2\n  |                                 ^"
+        )
+    );
+
+    let almanac = format!("seeds: {max} 0\n\nseed-to-location map:\n{} 0 2", max - 2)
+        .parse::<Almanac>()
+        .unwrap();
+    assert_eq!(
+        almanac.chain("seed", "location").unwrap().convert(1),
+        max - 1
+    );
+}
+
+#[test]
+fn day_05_locate_ranges_must_not_be_empty() {
+    let locate = |value: &str| {
+        let overrides = [("locate".to_string(), value.to_string())].into();
+        Params::new(solution("05").params(), &overrides).map_err(|err| err.to_string())
+    };
+
+    assert!(locate("3..50").is_ok());
+    assert!(locate("50").is_ok());
+    for value in ["50..3", "3..3"] {
+        assert_eq!(
+            locate(value).unwrap_err(),
+            format!("invalid value '{value}' for parameter 'locate': start must be below end")
+        );
+    }
+}
+
+#[test]
+fn day_05_preimages_agree_with_the_forward_solver() {
+    use crate::day_05::Almanac;
+
+    let almanac = read_input("05").parse::<Almanac>().unwrap();
+    let chain = almanac.chain("seed", "location").unwrap();
+    let seeds = almanac.seed_ranges();
+    let reaches = |locations| {
+        chain.preimage(locations).iter().any(|source| {
+            seeds
+                .iter()
+                .any(|seed| seed.start.max(source.start) < seed.end.min(source.end))
+        })
+    };
+
+    let lowest = 136_096_660;
+    assert!(reaches(lowest..lowest + 1));
+    assert!(!reaches(0..lowest));
+
+    assert!(chain.preimage(0..0).is_empty());
+    for location in [0, 1000, lowest, usize::MAX - 1] {
+        for mut source in chain.preimage(location..location + 1) {
+            assert!(source.all(|seed| chain.convert(seed) == location));
+        }
     }
+}
+
+#[test]
+fn day_06_ways_to_win_are_exact() {
+    use crate::day_06::ways_to_win;
+
+    // The races from the puzzle, the last with a perfect square discriminant
+    // where holding for 10 or 20 only ties with the record.
+    assert_eq!(ways_to_win(7, 9), Some(4));
+    assert_eq!(ways_to_win(15, 40), Some(8));
+    assert_eq!(ways_to_win(30, 200), Some(9));
 
-    day_test!("01", "55208", "54578");
-    day_test!("02", "2683", "49710");
-    day_test!("03", "531561", "83279367");
-    day_test!("04", "22193", "5625994");
-    day_test!("05", "825516882" /*, "136096660" */);
-    day_test!("06", "1108800", "36919753");
-    day_test!("07", "250058342", "250506580");
-    day_test!("08", "13207");
-    day_test!("09", "2175229206", "942");
-    day_test!("11", "9957702", "512240933238");
+    // A single hold that ties, one that wins, and records out of reach.
+    assert_eq!(ways_to_win(4, 4), Some(0));
+    assert_eq!(ways_to_win(4, 3), Some(1));
+    assert_eq!(ways_to_win(0, 0), Some(0));
+    assert_eq!(ways_to_win(10, 1000), Some(0));
+    assert_eq!(ways_to_win(10, u128::MAX), Some(0));
+    assert_eq!(ways_to_win(u128::MAX, 0), None);
+
+    let time = u128::from(u64::MAX);
+    assert_eq!(ways_to_win(time, 0), Some(time - 1));
+
+    // Each race fits, but the product of all three doesn't.
+    let input = format!("Time: {time} {time} {time}\nDistance: 0 0 0");
+    let status = runner::run(
+        "06",
+        solution("06"),
+        Part::One,
+        &input,
+        &Params::default(),
+        None,
+    )
+    .status;
+    assert!(
+        matches!(status, Status::Error(err) if err.to_string() == "the product of the ways to win overflows")
+    );
+
+    for time in 0..60 {
+        for record in 0..time * time / 4 + 2 {
+            let expected = (0..=time)
+                .filter(|hold| hold * (time - hold) > record)
+                .count();
+            assert_eq!(
+                ways_to_win(time, record),
+                Some(expected as u128),
+                "{time} {record}"
+            );
+        }
+    }
+}
+
+#[test]
+fn day_11_factors_too_large_for_the_distances_are_rejected() {
+    let run = |factor: usize| {
+        let overrides = [("factor".to_string(), factor.to_string())].into();
+        let params = Params::new(solution("11").params(), &overrides).unwrap();
+        runner::run(
+            "11",
+            solution("11"),
+            Part::Two,
+            "#..\n...\n..#",
+            &params,
+            None,
+        )
+        .status
+    };
+
+    assert!(matches!(run(10), Status::Ok(answer) if answer == 22_u8));
+    assert!(matches!(
+        run(usize::MAX),
+        Status::Error(err) if err.to_string() == "the expansion factor is too large"
+    ));
+}
+
+#[test]
+fn verify_fails_parts_that_have_an_expected_answer() {
+    assert!(Verdict::Mismatch.is_failure(true));
+    assert!(Verdict::InputChanged.is_failure(true));
+    assert!(!Verdict::Ok.is_failure(true));
+    assert!(!Verdict::Missing.is_failure(false));
+
+    for label in ["error", "not implemented", "panicked", "timed out"] {
+        assert!(Verdict::Failed(label).is_failure(true));
+        assert!(!Verdict::Failed(label).is_failure(false));
+    }
 }
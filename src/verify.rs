@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::{
+    answers::Answers,
+    input::InputSource,
+    params::Params,
+    registry, report,
+    runner::{self, Part, Status},
+};
+
+pub enum Verdict {
+    Ok,
+    Mismatch,
+    Missing,
+    Recorded,
+    InputChanged,
+    Failed(&'static str),
+}
+
+impl Verdict {
+    fn label(&self) -> &'static str {
+        match self {
+            Verdict::Ok => "ok",
+            Verdict::Mismatch => "mismatch",
+            Verdict::Missing => "missing",
+            Verdict::Recorded => "recorded",
+            Verdict::InputChanged => "input changed",
+            Verdict::Failed(label) => label,
+        }
+    }
+
+    /// Whether the verdict fails the verification. A part that didn't produce
+    /// an answer only fails when an answer is `expected` from it.
+    pub fn is_failure(&self, expected: bool) -> bool {
+        match self {
+            Verdict::Mismatch | Verdict::InputChanged => true,
+            Verdict::Failed(_) => expected,
+            Verdict::Ok | Verdict::Missing | Verdict::Recorded => false,
+        }
+    }
+}
+
+/// Runs every registered day and its examples and compares the answers with
+/// `answers.toml`. With `record`, answers for parts that have none yet are
+/// written back to the file.
+pub fn verify(record: bool, timeout: Option<Duration>) -> Result<()> {
+    let answers = Answers::load()?;
+    let mut rows = vec![];
+    let mut failures = 0;
+
+    for registration in registry::days()? {
+        let day = registration.id();
+        let known = answers.day(&day);
+        let params = Params::defaults(registration.solution.params());
+
+        for part in Part::ALL {
+            let expected = known.and_then(|known| known.get(part));
+
+            let (actual, verdict) = match InputSource::Day(day.clone()).load(registration.solution)
+            {
+                Err(err) => failure(&Status::Error(err)),
+                Ok(input) => {
+                    match runner::run(&day, registration.solution, part, &input, &params, timeout)
+                        .status
+                    {
+                        Status::Ok(answer) => {
+                            let verdict = match expected {
+                                _ if known.is_some_and(|known| !known.matches_input(&input)) => {
+                                    Verdict::InputChanged
+                                }
+                                Some(expected) if *expected == answer => Verdict::Ok,
+                                Some(_) => Verdict::Mismatch,
+                                None if record => {
+                                    Answers::record(&day, part, &answer, &input)?;
+                                    Verdict::Recorded
+                                }
+                                None => Verdict::Missing,
+                            };
+
+                            (answer.to_string(), verdict)
+                        }
+                        status => failure(&status),
+                    }
+                }
+            };
+
+            if verdict.is_failure(expected.is_some()) {
+                failures += 1;
+            }
+
+            rows.push(vec![
+                day.clone(),
+                part.to_string(),
+                expected.map(ToString::to_string).unwrap_or_default(),
+                actual,
+                verdict.label().to_string(),
+            ]);
+        }
+
+        for (name, example) in known.map(|known| &known.examples).into_iter().flatten() {
+            let source = example.source(&day, name);
+            let params = Params::new(registration.solution.params(), &example.param_overrides());
+
+            for part in Part::ALL {
+                let Some(expected) = example.get(part) else {
+                    continue;
+                };
+
+                let input = source.load(registration.solution);
+                let (actual, verdict) = match (input, &params) {
+                    (Err(err), _) => failure(&Status::Error(err)),
+                    (_, Err(err)) => failure(&Status::Error(anyhow!("{err:#}"))),
+                    (Ok(input), Ok(params)) => {
+                        match runner::run(
+                            &day,
+                            registration.solution,
+                            part,
+                            &input,
+                            params,
+                            timeout,
+                        )
+                        .status
+                        {
+                            Status::Ok(answer) if answer == *expected => {
+                                (answer.to_string(), Verdict::Ok)
+                            }
+                            Status::Ok(answer) => (answer.to_string(), Verdict::Mismatch),
+                            status => failure(&status),
+                        }
+                    }
+                };
+
+                if verdict.is_failure(true) {
+                    failures += 1;
+                }
+
+                rows.push(vec![
+                    format!("{day} {name}"),
+                    part.to_string(),
+                    expected.to_string(),
+                    actual,
+                    verdict.label().to_string(),
+                ]);
+            }
+        }
+    }
+
+    report::print_table(&["Day", "Part", "Expected", "Actual", "Status"], &rows);
+
+    if failures > 0 {
+        bail!("{failures} answer(s) failed or did not match answers.toml");
+    }
+
+    Ok(())
+}
+
+/// The actual value and verdict for a run that didn't produce an answer.
+fn failure(status: &Status) -> (String, Verdict) {
+    let actual = match status {
+        Status::Error(err) => format!("{err:#}"),
+        Status::Panicked(panic) => panic.to_string(),
+        _ => String::new(),
+    };
+
+    (actual, Verdict::Failed(status.label()))
+}